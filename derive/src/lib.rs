@@ -0,0 +1,141 @@
+//! Derive macros for the [`locspan`](https://crates.io/crates/locspan) crate.
+//!
+//! Those macros are re-exported by `locspan` when its `derive` feature is
+//! enabled, and should be used through it.
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod map_metadata;
+mod project;
+mod spanned;
+
+/// Derives the `Spanned` trait.
+///
+/// The span of a struct (or enum variant) is given by its field marked with
+/// the `#[locspan(span)]` attribute, if any.
+/// Otherwise, it is the union of the spans of all its fields, except the ones
+/// marked with `#[locspan(ignore)]`. In this case every considered field must
+/// implement `Spanned<Span = locspan::Span>`.
+///
+/// ```
+/// use locspan::{Loc, Location, Meta, Span, Spanned};
+/// # type FileId = usize;
+/// # type Literal = u32;
+/// # type Operator = char;
+/// # type Ident = String;
+///
+/// #[derive(Spanned)]
+/// enum Expr {
+///   Literal(Loc<Literal, FileId>),
+///   Binary(Loc<Box<Expr>, FileId>, Loc<Operator, FileId>, Loc<Box<Expr>, FileId>),
+///   Call {
+///     #[locspan(ignore)]
+///     resolved: bool,
+///     function: Loc<Ident, FileId>,
+///     args: Loc<Vec<Expr>, FileId>,
+///   },
+/// }
+///
+/// let at = |start, end| Location::new(0, Span::new(start, end));
+/// let expr = Expr::Binary(
+///   Meta(Box::new(Expr::Literal(Meta(1, at(0, 1)))), at(0, 1)),
+///   Meta('+', at(2, 3)),
+///   Meta(Box::new(Expr::Literal(Meta(2, at(4, 5)))), at(4, 5)),
+/// );
+///
+/// assert_eq!(expr.span(), Span::new(0, 5));
+/// ```
+#[proc_macro_derive(Spanned, attributes(locspan))]
+pub fn derive_spanned(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	match spanned::derive(input) {
+		Ok(tokens) => tokens.into(),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
+
+/// Derives the `MapMetadataRecursively` and `TryMapMetadataRecursively`
+/// traits.
+///
+/// The metadata type is the only type parameter of the type, or the one given
+/// by the `#[locspan(metadata = M)]` attribute. Fields are mapped as follows:
+/// - fields whose type does not mention the metadata type are left untouched;
+/// - fields of the metadata type are mapped with the mapping function;
+/// - `Meta<T, M>` fields where `T` does not mention the metadata type have
+///   their metadata mapped with the mapping function;
+/// - any other field is mapped recursively, and its type must implement the
+///   mapping traits.
+///
+/// ```
+/// use locspan::{MapMetadataRecursively, Meta, Span};
+///
+/// #[derive(MapMetadataRecursively, PartialEq, Debug)]
+/// enum Expr<M> {
+///   Literal(Meta<u32, M>),
+///   Neg(Box<Meta<Expr<M>, M>>),
+///   Call {
+///     function: Meta<String, M>,
+///     args: Vec<Meta<Expr<M>, M>>,
+///   },
+/// }
+///
+/// let expr = Expr::Neg(Box::new(Meta(Expr::Literal(Meta(1, 1)), 0)));
+/// let spanned: Expr<Span> = expr.map_metadata_recursively(|i| Span::new(i, i + 1));
+///
+/// assert_eq!(
+///   spanned,
+///   Expr::Neg(Box::new(Meta(Expr::Literal(Meta(1, Span::new(1, 2))), Span::new(0, 1))))
+/// );
+/// ```
+#[proc_macro_derive(MapMetadataRecursively, attributes(locspan))]
+pub fn derive_map_metadata_recursively(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	match map_metadata::derive(input) {
+		Ok(tokens) => tokens.into(),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
+
+/// Derives field projections for located values of a struct.
+///
+/// For a struct `Foo`, this generates a `FooProjection` trait, implemented
+/// for `Meta<Foo, M>`, providing for each field `name` (or `0`, `1`, etc. for
+/// tuple structs) the methods `project_name` and `project_name_mut`,
+/// borrowing the field along with the metadata.
+///
+/// ```
+/// use locspan::{Loc, Location, Meta, MetaProject, Span};
+/// # type FileId = usize;
+/// # type Ident = String;
+/// # type Expr = u32;
+///
+/// #[derive(MetaProject)]
+/// struct Binding {
+///   name: Loc<Ident, FileId>,
+///   value: Loc<Expr, FileId>,
+/// }
+///
+/// fn check(binding: &Loc<Binding, FileId>) -> Span {
+///   let Meta(value, _location) = binding.project_value();
+///   value.location().span()
+/// }
+///
+/// let at = |start, end| Location::new(0, Span::new(start, end));
+/// let binding = Meta(
+///   Binding {
+///     name: Meta("x".to_string(), at(4, 5)),
+///     value: Meta(1, at(8, 9)),
+///   },
+///   at(0, 10),
+/// );
+///
+/// assert_eq!(check(&binding), Span::new(8, 9));
+/// ```
+#[proc_macro_derive(MetaProject)]
+pub fn derive_meta_project(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	match project::derive(input) {
+		Ok(tokens) => tokens.into(),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
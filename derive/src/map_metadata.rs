@@ -0,0 +1,279 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+	parse_quote, Data, DeriveInput, Fields, GenericArgument, GenericParam, Ident, PathArguments,
+	Type,
+};
+
+/// Finds the metadata type parameter.
+///
+/// It is given by the `#[locspan(metadata = M)]` container attribute, or is
+/// the only type parameter of the type.
+fn metadata_parameter(input: &DeriveInput) -> syn::Result<Ident> {
+	let mut result = None;
+
+	for attr in &input.attrs {
+		if attr.path().is_ident("locspan") {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("metadata") {
+					if result.is_some() {
+						return Err(meta.error("duplicate `metadata` attribute"));
+					}
+
+					result = Some(meta.value()?.parse::<Ident>()?);
+					Ok(())
+				} else {
+					Err(meta.error("unknown `locspan` attribute"))
+				}
+			})?
+		}
+	}
+
+	match result {
+		Some(ident) => {
+			if input.generics.type_params().any(|p| p.ident == ident) {
+				Ok(ident)
+			} else {
+				Err(syn::Error::new(
+					ident.span(),
+					"metadata must be a type parameter",
+				))
+			}
+		}
+		None => {
+			let mut params = input.generics.type_params();
+			match (params.next(), params.next()) {
+				(Some(param), None) => Ok(param.ident.clone()),
+				_ => Err(syn::Error::new(
+					input.ident.span(),
+					"cannot find the metadata type parameter, use `#[locspan(metadata = M)]`",
+				)),
+			}
+		}
+	}
+}
+
+/// Checks if the given tokens mention the given identifier.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+	tokens.into_iter().any(|t| match t {
+		TokenTree::Ident(i) => i == *ident,
+		TokenTree::Group(g) => mentions(g.stream(), ident),
+		_ => false,
+	})
+}
+
+/// How a field is mapped.
+enum Mapping {
+	/// The field does not contain any metadata, it is moved as is.
+	Move,
+
+	/// The field is the metadata itself.
+	Metadata,
+
+	/// The field is a `Meta<T, M>` where `T` does not contain any metadata.
+	Leaf,
+
+	/// The field is mapped recursively.
+	Recursive,
+}
+
+impl Mapping {
+	fn new(ty: &Type, m: &Ident) -> Self {
+		if !mentions(quote!(#ty), m) {
+			return Self::Move;
+		}
+
+		if let Type::Path(path) = ty {
+			if path.qself.is_none() {
+				if path.path.is_ident(m) {
+					return Self::Metadata;
+				}
+
+				let last = path.path.segments.last().unwrap();
+				if last.ident == "Meta" {
+					if let PathArguments::AngleBracketed(args) = &last.arguments {
+						let args: Vec<_> = args.args.iter().collect();
+						if let [GenericArgument::Type(value), GenericArgument::Type(Type::Path(metadata))] =
+							args.as_slice()
+						{
+							if metadata.qself.is_none()
+								&& metadata.path.is_ident(m)
+								&& !mentions(quote!(#value), m)
+							{
+								return Self::Leaf;
+							}
+						}
+					}
+				}
+			}
+		}
+
+		Self::Recursive
+	}
+}
+
+/// Struct or enum variant.
+struct Variant {
+	/// Pattern matching the struct or variant.
+	pattern: TokenStream,
+
+	/// Expression building the mapped struct or variant.
+	map: TokenStream,
+
+	/// Expression building the mapped struct or variant, fallibly.
+	try_map: TokenStream,
+}
+
+impl Variant {
+	fn new(path: TokenStream, fields: &Fields, m: &Ident) -> Self {
+		let mut bindings = Vec::new();
+		let mut mapped = Vec::new();
+		let mut try_mapped = Vec::new();
+
+		for (i, field) in fields.iter().enumerate() {
+			let binding = format_ident!("__locspan_{}", i);
+
+			let (map, try_map) = match Mapping::new(&field.ty, m) {
+				Mapping::Move => (quote!(#binding), quote!(#binding)),
+				Mapping::Metadata => (quote!(f(#binding)), quote!(f(#binding)?)),
+				Mapping::Leaf => (
+					quote!({
+						let ::locspan::Meta(value, metadata) = #binding;
+						::locspan::Meta(value, f(metadata))
+					}),
+					quote!({
+						let ::locspan::Meta(value, metadata) = #binding;
+						::locspan::Meta(value, f(metadata)?)
+					}),
+				),
+				Mapping::Recursive => (
+					quote!(::locspan::MapMetadataRecursively::map_metadata_recursively_mut_ref(#binding, f)),
+					quote!(::locspan::TryMapMetadataRecursively::try_map_metadata_recursively_mut_ref(#binding, f)?),
+				),
+			};
+
+			bindings.push(binding);
+			mapped.push(map);
+			try_mapped.push(try_map);
+		}
+
+		let (pattern, map, try_map) = match fields {
+			Fields::Named(named) => {
+				let names: Vec<_> = named
+					.named
+					.iter()
+					.map(|f| f.ident.as_ref().unwrap())
+					.collect();
+				(
+					quote!(#path { #(#names: #bindings),* }),
+					quote!(#path { #(#names: #mapped),* }),
+					quote!(#path { #(#names: #try_mapped),* }),
+				)
+			}
+			Fields::Unnamed(_) => (
+				quote!(#path ( #(#bindings),* )),
+				quote!(#path ( #(#mapped),* )),
+				quote!(#path ( #(#try_mapped),* )),
+			),
+			Fields::Unit => (quote!(#path), quote!(#path), quote!(#path)),
+		};
+
+		Self {
+			pattern,
+			map,
+			try_map,
+		}
+	}
+}
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+	let m = metadata_parameter(&input)?;
+	let ident = &input.ident;
+
+	let variants = match &input.data {
+		Data::Struct(s) => vec![Variant::new(quote!(#ident), &s.fields, &m)],
+		Data::Enum(e) => e
+			.variants
+			.iter()
+			.map(|v| {
+				let v_ident = &v.ident;
+				Variant::new(quote!(#ident::#v_ident), &v.fields, &m)
+			})
+			.collect(),
+		Data::Union(_) => {
+			return Err(syn::Error::new(
+				input.ident.span(),
+				"cannot derive `MapMetadataRecursively` for unions",
+			))
+		}
+	};
+
+	let n = Ident::new("__LocspanN", Span::call_site());
+	let e = Ident::new("__LocspanE", Span::call_site());
+
+	let output_args = input.generics.params.iter().map(|p| match p {
+		GenericParam::Lifetime(l) => {
+			let l = &l.lifetime;
+			quote!(#l)
+		}
+		GenericParam::Type(t) if t.ident == m => quote!(#n),
+		GenericParam::Type(t) => {
+			let t = &t.ident;
+			quote!(#t)
+		}
+		GenericParam::Const(c) => {
+			let c = &c.ident;
+			quote!(#c)
+		}
+	});
+	let output = quote!(#ident < #(#output_args),* >);
+
+	let patterns: Vec<_> = variants.iter().map(|v| &v.pattern).collect();
+	let maps = variants.iter().map(|v| &v.map);
+	let try_maps = variants.iter().map(|v| &v.try_map);
+
+	let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let mut map_generics = input.generics.clone();
+	map_generics.params.push(parse_quote!(#n));
+	let (map_impl_generics, _, _) = map_generics.split_for_impl();
+
+	let mut try_map_generics = map_generics.clone();
+	try_map_generics.params.push(parse_quote!(#e));
+	let (try_map_impl_generics, _, _) = try_map_generics.split_for_impl();
+
+	Ok(quote! {
+		impl #map_impl_generics ::locspan::MapMetadataRecursively<#m, #n> for #ident #ty_generics
+		#where_clause
+		{
+			type Output = #output;
+
+			fn map_metadata_recursively_mut_ref<__LocspanF>(self, f: &mut __LocspanF) -> Self::Output
+			where
+				__LocspanF: FnMut(#m) -> #n,
+			{
+				match self {
+					#(#patterns => #maps,)*
+				}
+			}
+		}
+
+		impl #try_map_impl_generics ::locspan::TryMapMetadataRecursively<#m, #n, #e> for #ident #ty_generics
+		#where_clause
+		{
+			type Output = #output;
+
+			fn try_map_metadata_recursively_mut_ref<__LocspanF>(
+				self,
+				f: &mut __LocspanF,
+			) -> ::core::result::Result<Self::Output, #e>
+			where
+				__LocspanF: FnMut(#m) -> ::core::result::Result<#n, #e>,
+			{
+				::core::result::Result::Ok(match self {
+					#(#patterns => #try_maps,)*
+				})
+			}
+		}
+	})
+}
@@ -0,0 +1,82 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Ident, Index};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+	let fields = match &input.data {
+		Data::Struct(s) => &s.fields,
+		_ => {
+			return Err(syn::Error::new(
+				input.ident.span(),
+				"`MetaProject` can only be derived for structs",
+			))
+		}
+	};
+
+	let ident = &input.ident;
+	let vis = &input.vis;
+	let trait_ident = format_ident!("{}Projection", ident);
+	let m = Ident::new("__LocspanM", Span::call_site());
+
+	let mut signatures = Vec::new();
+	let mut methods = Vec::new();
+	for (i, field) in fields.iter().enumerate() {
+		let (method, method_mut, access) = match &field.ident {
+			Some(name) => (
+				format_ident!("project_{}", name),
+				format_ident!("project_{}_mut", name),
+				quote!(#name),
+			),
+			None => {
+				let index = Index::from(i);
+				(
+					format_ident!("project_{}", i),
+					format_ident!("project_{}_mut", i),
+					quote!(#index),
+				)
+			}
+		};
+
+		let ty = &field.ty;
+		let doc = format!("Borrows the `{access}` field along with the metadata.");
+		let doc_mut = format!("Mutably borrows the `{access}` field along with the metadata.");
+
+		signatures.push(quote! {
+			#[doc = #doc]
+			fn #method(&self) -> ::locspan::Meta<&#ty, &#m>;
+
+			#[doc = #doc_mut]
+			fn #method_mut(&mut self) -> ::locspan::Meta<&mut #ty, &#m>;
+		});
+
+		methods.push(quote! {
+			#[inline(always)]
+			fn #method(&self) -> ::locspan::Meta<&#ty, &#m> {
+				::locspan::Meta(&self.0.#access, &self.1)
+			}
+
+			#[inline(always)]
+			fn #method_mut(&mut self) -> ::locspan::Meta<&mut #ty, &#m> {
+				::locspan::Meta(&mut self.0.#access, &self.1)
+			}
+		});
+	}
+
+	let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+	let mut generics = input.generics.clone();
+	generics.params.push(parse_quote!(#m));
+	let (impl_generics, trait_generics, _) = generics.split_for_impl();
+
+	let trait_doc = format!("Field projections of located [`{ident}`] values.");
+
+	Ok(quote! {
+		#[doc = #trait_doc]
+		#vis trait #trait_ident #impl_generics #where_clause {
+			#(#signatures)*
+		}
+
+		impl #impl_generics #trait_ident #trait_generics for ::locspan::Meta<#ident #ty_generics, #m> #where_clause {
+			#(#methods)*
+		}
+	})
+}
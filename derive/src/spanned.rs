@@ -0,0 +1,201 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, Type};
+
+/// Field attribute.
+enum FieldAttribute {
+	/// No attribute.
+	None,
+
+	/// `#[locspan(span)]`: the field gives the span of its parent.
+	Span,
+
+	/// `#[locspan(ignore)]`: the field is not considered.
+	Ignore,
+}
+
+impl FieldAttribute {
+	fn parse(field: &syn::Field) -> syn::Result<Self> {
+		let mut result = Self::None;
+
+		for attr in &field.attrs {
+			if attr.path().is_ident("locspan") {
+				attr.parse_nested_meta(|meta| {
+					if !matches!(result, Self::None) {
+						return Err(meta.error("duplicate `locspan` attribute"));
+					}
+
+					if meta.path.is_ident("span") {
+						result = Self::Span;
+						Ok(())
+					} else if meta.path.is_ident("ignore") {
+						result = Self::Ignore;
+						Ok(())
+					} else {
+						Err(meta.error("unknown `locspan` attribute"))
+					}
+				})?
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+/// How the span of a struct or variant is computed.
+enum Method<'a> {
+	/// Delegates to the given field.
+	Delegate(Ident, &'a Type),
+
+	/// Union of the spans of the given fields.
+	Union(Vec<(Ident, &'a Type)>),
+}
+
+/// Struct or enum variant.
+struct Variant<'a> {
+	/// Pattern matching the struct or variant.
+	pattern: TokenStream,
+
+	/// Span computation method.
+	method: Method<'a>,
+}
+
+impl<'a> Variant<'a> {
+	fn new(path: TokenStream, fields: &'a Fields, span: Span) -> syn::Result<Self> {
+		let mut bindings = Vec::new();
+		let mut delegate = None;
+		let mut union = Vec::new();
+
+		for (i, field) in fields.iter().enumerate() {
+			let binding = format_ident!("__locspan_{}", i);
+
+			match FieldAttribute::parse(field)? {
+				FieldAttribute::None => union.push((binding.clone(), &field.ty)),
+				FieldAttribute::Span => {
+					if delegate.is_some() {
+						return Err(syn::Error::new_spanned(
+							field,
+							"multiple fields marked with `#[locspan(span)]`",
+						));
+					}
+
+					delegate = Some((binding.clone(), &field.ty))
+				}
+				FieldAttribute::Ignore => (),
+			}
+
+			bindings.push(binding)
+		}
+
+		let pattern = match fields {
+			Fields::Named(named) => {
+				let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+				quote!(#path { #(#names: #bindings),* })
+			}
+			Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+			Fields::Unit => quote!(#path),
+		};
+
+		let method = match delegate {
+			Some((binding, ty)) => Method::Delegate(binding, ty),
+			None if union.is_empty() => {
+				return Err(syn::Error::new(
+					span,
+					"cannot derive `Spanned` without any spanned field",
+				))
+			}
+			None => Method::Union(union),
+		};
+
+		Ok(Self { pattern, method })
+	}
+}
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+	let variants = match &input.data {
+		Data::Struct(s) => vec![Variant::new(quote!(Self), &s.fields, input.ident.span())?],
+		Data::Enum(e) => e
+			.variants
+			.iter()
+			.map(|v| {
+				let ident = &v.ident;
+				Variant::new(quote!(Self::#ident), &v.fields, ident.span())
+			})
+			.collect::<syn::Result<_>>()?,
+		Data::Union(_) => {
+			return Err(syn::Error::new(
+				input.ident.span(),
+				"cannot derive `Spanned` for unions",
+			))
+		}
+	};
+
+	// If every variant delegates its span, use the span type of the first
+	// delegate. Otherwise spans are merged and must be `locspan::Span`.
+	let first_delegate = variants.iter().try_fold(None, |first, v| match &v.method {
+		Method::Delegate(_, ty) => Some(first.or(Some(*ty))),
+		Method::Union(_) => None,
+	});
+	let span_type = match first_delegate {
+		Some(Some(ty)) => quote!(<#ty as ::locspan::Spanned>::Span),
+		_ => quote!(::locspan::Span),
+	};
+	let first_delegate = first_delegate.flatten().map(|ty| quote!(#ty).to_string());
+
+	let mut predicates = Vec::new();
+	let mut arms = Vec::new();
+	for variant in &variants {
+		let pattern = &variant.pattern;
+		let body = match &variant.method {
+			Method::Delegate(binding, ty) => {
+				if first_delegate.as_ref() == Some(&quote!(#ty).to_string()) {
+					predicates.push(quote!(#ty: ::locspan::Spanned))
+				} else {
+					predicates.push(quote!(#ty: ::locspan::Spanned<Span = #span_type>))
+				}
+
+				quote!(::locspan::Spanned::span(#binding))
+			}
+			Method::Union(fields) => {
+				let (first, _) = &fields[0];
+				let rest = fields[1..].iter().map(|(binding, _)| binding);
+				predicates.extend(
+					fields
+						.iter()
+						.map(|(_, ty)| quote!(#ty: ::locspan::Spanned<Span = ::locspan::Span>)),
+				);
+				quote! {
+					{
+						let mut span = ::locspan::Spanned::span(#first);
+						#(span.append(::locspan::Spanned::span(#rest));)*
+						span
+					}
+				}
+			}
+		};
+
+		arms.push(quote!(#pattern => #body))
+	}
+
+	let ident = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let mut predicates_with_clause = where_clause
+		.map(|w| w.predicates.iter().map(|p| quote!(#p)).collect())
+		.unwrap_or_else(Vec::new);
+	predicates_with_clause.extend(predicates);
+
+	Ok(quote! {
+		impl #impl_generics ::locspan::Spanned for #ident #ty_generics
+		where
+			#(#predicates_with_clause,)*
+		{
+			type Span = #span_type;
+
+			fn span(&self) -> Self::Span {
+				match self {
+					#(#arms,)*
+				}
+			}
+		}
+	})
+}
@@ -0,0 +1,119 @@
+use crate::WithMetadata;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+
+/// Wrapper to compare, order and hash values by their metadata only.
+///
+/// This is the dual of [`Stripped`](crate::Stripped): the wrapped value is
+/// ignored and only the metadata is considered. It can be used to sort
+/// diagnostics or tokens by location, or as a key to deduplicate values
+/// found at the same location.
+///
+/// ```
+/// use locspan::{ByMetadata, Meta, Span};
+///
+/// let a = ByMetadata(Meta("foo", Span::new(0, 3)));
+/// let b = ByMetadata(Meta("bar", Span::new(0, 3)));
+/// assert_eq!(a, b);
+/// assert!(a < ByMetadata(Meta("foo", Span::new(4, 7))));
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct ByMetadata<T>(pub T);
+
+impl<T> ByMetadata<T> {
+	#[inline]
+	pub fn unwrap(self) -> T {
+		self.0
+	}
+}
+
+impl<T> Deref for ByMetadata<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for ByMetadata<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<T: WithMetadata> PartialEq for ByMetadata<T>
+where
+	T::Metadata: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.0.metadata() == other.0.metadata()
+	}
+}
+
+impl<T: WithMetadata> Eq for ByMetadata<T> where T::Metadata: Eq {}
+
+impl<T: WithMetadata> PartialOrd for ByMetadata<T>
+where
+	T::Metadata: PartialOrd,
+{
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		self.0.metadata().partial_cmp(other.0.metadata())
+	}
+}
+
+impl<T: WithMetadata> Ord for ByMetadata<T>
+where
+	T::Metadata: Ord,
+{
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.0.metadata().cmp(other.0.metadata())
+	}
+}
+
+impl<T: WithMetadata> Hash for ByMetadata<T>
+where
+	T::Metadata: Hash,
+{
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.metadata().hash(state)
+	}
+}
+
+/// Sorting functions for slices of values with metadata (such as [`Meta`]
+/// values), by metadata.
+///
+/// [`Meta`]: crate::Meta
+pub trait SortByMetadata {
+	/// Sorts the slice by metadata, preserving the order of values with
+	/// equal metadata.
+	///
+	/// ```
+	/// use locspan::{Meta, SortByMetadata, Span};
+	///
+	/// let mut tokens = vec![Meta('b', Span::new(2, 3)), Meta('a', Span::new(0, 1))];
+	/// tokens.sort_by_metadata();
+	/// assert_eq!(tokens, [Meta('a', Span::new(0, 1)), Meta('b', Span::new(2, 3))]);
+	/// ```
+	fn sort_by_metadata(&mut self);
+
+	/// Sorts the slice by metadata, without preserving the order of values
+	/// with equal metadata.
+	fn sort_unstable_by_metadata(&mut self);
+}
+
+impl<T: WithMetadata> SortByMetadata for [T]
+where
+	T::Metadata: Ord,
+{
+	#[inline(always)]
+	fn sort_by_metadata(&mut self) {
+		self.sort_by(|a, b| a.metadata().cmp(b.metadata()))
+	}
+
+	#[inline(always)]
+	fn sort_unstable_by_metadata(&mut self) {
+		self.sort_unstable_by(|a, b| a.metadata().cmp(b.metadata()))
+	}
+}
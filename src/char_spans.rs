@@ -0,0 +1,74 @@
+use crate::{Meta, Span};
+use std::str::CharIndices;
+
+/// Extension trait to iterate over the characters of a string with their
+/// span.
+pub trait SpannedChars {
+	/// Returns an iterator over the characters of the string, with their
+	/// span.
+	///
+	/// This is similar to [`str::char_indices`], but yields the full span of
+	/// each character instead of its start position.
+	///
+	/// ```
+	/// use locspan::{Meta, Span, SpannedChars};
+	///
+	/// let mut chars = "aé".char_spans();
+	/// assert_eq!(chars.next(), Some(Meta('a', Span::new(0, 1))));
+	/// assert_eq!(chars.next(), Some(Meta('é', Span::new(1, 3))));
+	/// assert_eq!(chars.next(), None);
+	/// ```
+	fn char_spans(&self) -> CharSpans<'_>;
+}
+
+impl SpannedChars for str {
+	#[inline(always)]
+	fn char_spans(&self) -> CharSpans<'_> {
+		CharSpans(self.char_indices())
+	}
+}
+
+/// Iterator over the characters of a string with their span.
+///
+/// Created by the [`SpannedChars::char_spans`] method.
+#[derive(Clone, Debug)]
+pub struct CharSpans<'a>(CharIndices<'a>);
+
+impl<'a> CharSpans<'a> {
+	/// Returns the remaining string.
+	#[inline(always)]
+	pub fn as_str(&self) -> &'a str {
+		self.0.as_str()
+	}
+
+	/// Returns the position of the next character, or the length of the
+	/// string if there is none.
+	#[inline(always)]
+	pub fn offset(&self) -> usize {
+		self.0.offset()
+	}
+}
+
+impl Iterator for CharSpans<'_> {
+	type Item = Meta<char, Span>;
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0
+			.next()
+			.map(|(i, c)| Meta(c, Span::with_len(i, c.len_utf8())))
+	}
+}
+
+impl DoubleEndedIterator for CharSpans<'_> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.0
+			.next_back()
+			.map(|(i, c)| Meta(c, Span::with_len(i, c.len_utf8())))
+	}
+}
+
+impl std::iter::FusedIterator for CharSpans<'_> {}
@@ -0,0 +1,43 @@
+use crate::{Location, Span};
+use std::num::TryFromIntError;
+
+impl From<codespan::Span> for Span {
+	fn from(span: codespan::Span) -> Self {
+		Self::new(span.start().into(), span.end().into())
+	}
+}
+
+/// Fails if one of the span bounds does not fit in a `u32`.
+impl TryFrom<Span> for codespan::Span {
+	type Error = TryFromIntError;
+
+	fn try_from(span: Span) -> Result<Self, Self::Error> {
+		Ok(codespan::Span::new(
+			u32::try_from(span.start())?,
+			u32::try_from(span.end())?,
+		))
+	}
+}
+
+/// Creates an empty span at the given position.
+impl From<codespan::ByteIndex> for Span {
+	fn from(index: codespan::ByteIndex) -> Self {
+		usize::from(index).into()
+	}
+}
+
+impl Location<codespan::FileId> {
+	/// Creates a location from a `codespan` file identifier and span.
+	#[inline(always)]
+	pub fn from_codespan(file: codespan::FileId, span: codespan::Span) -> Self {
+		Self::new(file, span.into())
+	}
+
+	/// Converts this location into a `codespan` file identifier and span.
+	///
+	/// Fails if one of the span bounds does not fit in a `u32`.
+	#[inline(always)]
+	pub fn to_codespan(&self) -> Result<(codespan::FileId, codespan::Span), TryFromIntError> {
+		Ok((*self.file(), self.span().try_into()?))
+	}
+}
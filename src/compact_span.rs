@@ -0,0 +1,242 @@
+use crate::{MaybeSpanned, Merge, Span, Spanned};
+use std::fmt;
+use std::num::NonZeroU32;
+use std::ops::Range;
+
+/// Compact range of bytes in a source file.
+///
+/// This is a smaller alternative to [`Span`], storing its start position and
+/// length as `u32`, for sources up to 4GiB.
+/// It uses a niche so that `Option<CompactSpan>` has the same size as
+/// `CompactSpan`, keeping the size of AST nodes carrying spans in check.
+///
+/// ```
+/// use locspan::CompactSpan;
+/// use std::mem::size_of;
+///
+/// assert_eq!(size_of::<CompactSpan>(), 8);
+/// assert_eq!(size_of::<Option<CompactSpan>>(), 8);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompactSpan {
+	/// Start byte (included), plus one.
+	start: NonZeroU32,
+
+	/// Length in bytes.
+	len: u32,
+}
+
+impl CompactSpan {
+	/// Creates a new `CompactSpan` by providing the index of its starting byte
+	/// (included) and ending byte (excluded).
+	///
+	/// If the `end` position is lower than the `start` position,
+	/// then the `start` position is used as ending position instead.
+	///
+	/// ## Panics
+	///
+	/// Panics if `start` is `u32::MAX`.
+	#[inline(always)]
+	pub fn new(start: u32, end: u32) -> Self {
+		Self {
+			start: NonZeroU32::new(start.checked_add(1).expect("span start overflow")).unwrap(),
+			len: end.saturating_sub(start),
+		}
+	}
+
+	/// Creates a new `CompactSpan` by providing the index of its starting byte
+	/// and its length in bytes.
+	///
+	/// ## Panics
+	///
+	/// Panics if the end position of the span overflows `u32`, or if `start`
+	/// is `u32::MAX`.
+	#[inline(always)]
+	pub fn with_len(start: u32, len: u32) -> Self {
+		Self::new(start, start.checked_add(len).expect("span end overflow"))
+	}
+
+	/// Size of the span in bytes.
+	#[inline(always)]
+	pub fn len(&self) -> u32 {
+		self.len
+	}
+
+	/// Checks if the span is empty.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the index of the first byte in the span.
+	#[inline(always)]
+	pub fn start(&self) -> u32 {
+		self.start.get() - 1
+	}
+
+	/// Returns the index of the first byte *after* the span.
+	///
+	/// This can never be lower than [`Self::start`].
+	#[inline(always)]
+	pub fn end(&self) -> u32 {
+		self.start() + self.len
+	}
+
+	/// Returns the range of bytes inside the span.
+	#[inline(always)]
+	pub fn range(&self) -> Range<usize> {
+		self.start() as usize..self.end() as usize
+	}
+
+	/// Sets the index of the first byte in the span.
+	///
+	/// If the end position of the span is lower that `start`,
+	/// then it is also changed into `start`.
+	#[inline(always)]
+	pub fn set_start(&mut self, start: u32) {
+		*self = Self::new(start, self.end())
+	}
+
+	/// Sets the index of the first byte *after* the span.
+	///
+	/// If `end` is lower that the start position, the start position is used instead.
+	#[inline(always)]
+	pub fn set_end(&mut self, end: u32) {
+		self.len = end.saturating_sub(self.start())
+	}
+
+	/// Computes the union of two spans.
+	///
+	/// If the two spans do not overlap,
+	/// all the positions in between will be included in the resulting span.
+	#[inline(always)]
+	pub fn union(&self, other: Self) -> Self {
+		Self::new(
+			std::cmp::min(self.start(), other.start()),
+			std::cmp::max(self.end(), other.end()),
+		)
+	}
+
+	/// Extend this span to include `other`.
+	///
+	/// This is the *in-place* version of [`union`](Self::union).
+	#[inline(always)]
+	pub fn append(&mut self, other: Self) {
+		*self = self.union(other)
+	}
+
+	/// Computes the intersection of two spans.
+	///
+	/// If the two spans do not overlap, then the empty span located at the start
+	/// of the most advanced span (maximum of the start of the two spans) is returned.
+	#[inline(always)]
+	pub fn inter(&self, other: Self) -> Self {
+		let start = std::cmp::max(self.start(), other.start());
+		Self::new(start, std::cmp::min(self.end(), other.end()))
+	}
+
+	/// Clear the span by moving its start position to its end position.
+	#[inline(always)]
+	pub fn clear(&mut self) {
+		*self = self.next()
+	}
+
+	/// Return the "next" span: the empty span starting at the end of this one.
+	#[inline(always)]
+	pub fn next(&self) -> Self {
+		Self::new(self.end(), self.end())
+	}
+
+	/// Push `count` bytes to the span.
+	///
+	/// Move its end position by `count`.
+	#[inline(always)]
+	pub fn push(&mut self, count: u32) {
+		self.set_end(self.end().checked_add(count).expect("span end overflow"))
+	}
+}
+
+impl fmt::Debug for CompactSpan {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("CompactSpan")
+			.field("start", &self.start())
+			.field("end", &self.end())
+			.finish()
+	}
+}
+
+impl fmt::Display for CompactSpan {
+	/// Formats the span as `start..end`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}..{}", self.start(), self.end())
+	}
+}
+
+impl Default for CompactSpan {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new(0, 0)
+	}
+}
+
+/// Error returned when converting a [`Span`] that does not fit in a
+/// [`CompactSpan`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CompactSpanOverflow;
+
+impl fmt::Display for CompactSpanOverflow {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "span too large for a compact span")
+	}
+}
+
+impl std::error::Error for CompactSpanOverflow {}
+
+impl TryFrom<Span> for CompactSpan {
+	type Error = CompactSpanOverflow;
+
+	#[inline(always)]
+	fn try_from(span: Span) -> Result<Self, Self::Error> {
+		match (u32::try_from(span.start()), u32::try_from(span.end())) {
+			(Ok(start), Ok(end)) if start < u32::MAX => Ok(Self::new(start, end)),
+			_ => Err(CompactSpanOverflow),
+		}
+	}
+}
+
+impl From<CompactSpan> for Span {
+	#[inline(always)]
+	fn from(span: CompactSpan) -> Self {
+		Self::new(span.start() as usize, span.end() as usize)
+	}
+}
+
+impl From<CompactSpan> for Range<usize> {
+	#[inline(always)]
+	fn from(span: CompactSpan) -> Self {
+		span.range()
+	}
+}
+
+impl Merge for CompactSpan {
+	#[inline(always)]
+	fn merge(self, other: Self) -> Self {
+		self.union(other)
+	}
+}
+
+impl Spanned for CompactSpan {
+	type Span = Self;
+
+	fn span(&self) -> Self::Span {
+		*self
+	}
+}
+
+impl MaybeSpanned for CompactSpan {
+	type Span = Self;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		Some(*self)
+	}
+}
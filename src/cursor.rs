@@ -0,0 +1,192 @@
+use crate::{Merge, Meta};
+use std::borrow::Cow;
+
+/// Tree node whose children are annotated with metadata.
+pub trait Children<M>: Sized {
+	/// Returns the children of the node.
+	fn children(&self) -> &[Meta<Self, M>];
+
+	/// Returns the children of the node, mutably.
+	fn children_mut(&mut self) -> &mut [Meta<Self, M>];
+}
+
+/// Clone-on-write cursor to navigate and edit a tree of `Meta` annotated
+/// nodes.
+///
+/// The cursor points to a node of the tree, starting from its root.
+/// The original tree is never modified: editing a node clones it, and
+/// leaving a modified node with [`up`](Self::up) clones its parent to store
+/// the new child. Only the nodes on the paths to the edited nodes are
+/// rebuilt. The metadata of each rebuilt parent is recomputed as the merge
+/// (using the [`Merge`] trait) of the metadata of all its children, so that
+/// ancestors stay consistent when a child grows, shrinks or is replaced.
+///
+/// The edited tree is returned by [`finish`](Self::finish).
+///
+/// ```
+/// use locspan::{Children, Cursor, Meta, Span};
+///
+/// #[derive(Clone, Debug)]
+/// struct Node(Vec<Meta<Node, Span>>);
+///
+/// impl Children<Span> for Node {
+///   fn children(&self) -> &[Meta<Node, Span>] {
+///     &self.0
+///   }
+///
+///   fn children_mut(&mut self) -> &mut [Meta<Node, Span>] {
+///     &mut self.0
+///   }
+/// }
+///
+/// let leaf = |start, end| Meta(Node(Vec::new()), Span::new(start, end));
+/// let tree = Meta(Node(vec![leaf(0, 2), leaf(3, 10)]), Span::new(0, 10));
+///
+/// let mut cursor = Cursor::new(&tree);
+/// cursor.enter(1);
+/// cursor.replace_metadata(Span::new(3, 5));
+/// let edited = cursor.finish();
+///
+/// assert_eq!(edited.1, Span::new(0, 5));
+/// assert_eq!(edited.0 .0[1].1, Span::new(3, 5));
+/// assert_eq!(tree.1, Span::new(0, 10));
+/// ```
+pub struct Cursor<'a, T: Clone, M: Clone> {
+	/// Ancestors of the current node, from the root, and whether or not they
+	/// were modified.
+	ancestors: Vec<(Cow<'a, Meta<T, M>>, bool)>,
+
+	/// Path from the root to the current node.
+	path: Vec<usize>,
+
+	/// Current node.
+	current: Cow<'a, Meta<T, M>>,
+
+	/// Whether or not the current node was modified.
+	modified: bool,
+}
+
+impl<'a, T: Clone + Children<M>, M: Clone + Merge> Cursor<'a, T, M> {
+	/// Creates a new cursor pointing to the root of the given tree.
+	pub fn new(root: &'a Meta<T, M>) -> Self {
+		Self {
+			ancestors: Vec::new(),
+			path: Vec::new(),
+			current: Cow::Borrowed(root),
+			modified: false,
+		}
+	}
+
+	/// Returns the path from the root to the current node, as a list of
+	/// child indexes.
+	#[inline(always)]
+	pub fn path(&self) -> &[usize] {
+		&self.path
+	}
+
+	/// Returns the depth of the current node, the root being at depth 0.
+	#[inline(always)]
+	pub fn depth(&self) -> usize {
+		self.path.len()
+	}
+
+	/// Returns the current node.
+	#[inline(always)]
+	pub fn node(&self) -> &Meta<T, M> {
+		&self.current
+	}
+
+	fn node_mut(&mut self) -> &mut Meta<T, M> {
+		self.modified = true;
+		self.current.to_mut()
+	}
+
+	/// Moves the cursor to the `i`-th child of the current node.
+	///
+	/// Returns `false` and leaves the cursor unchanged if there is no such
+	/// child.
+	pub fn enter(&mut self, i: usize) -> bool {
+		let child = match &self.current {
+			Cow::Borrowed(node) => {
+				let node: &'a Meta<T, M> = node;
+				node.0.children().get(i).map(Cow::Borrowed)
+			}
+			Cow::Owned(node) => node.0.children().get(i).cloned().map(Cow::Owned),
+		};
+
+		match child {
+			Some(child) => {
+				let parent = std::mem::replace(&mut self.current, child);
+				self.ancestors.push((parent, self.modified));
+				self.path.push(i);
+				self.modified = false;
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Moves the cursor to the parent of the current node.
+	///
+	/// If the current node was modified, the parent is rebuilt with the new
+	/// node, and its metadata is recomputed as the merge of the metadata of
+	/// all its children.
+	/// Returns `false` and leaves the cursor unchanged if the current node is
+	/// the root.
+	pub fn up(&mut self) -> bool {
+		let (parent, parent_modified) = match self.ancestors.pop() {
+			Some(parent) => parent,
+			None => return false,
+		};
+
+		let i = self.path.pop().unwrap();
+		let child = std::mem::replace(&mut self.current, parent);
+		let child_modified = std::mem::replace(&mut self.modified, parent_modified);
+
+		if child_modified {
+			let parent = self.node_mut();
+			parent.0.children_mut()[i] = child.into_owned();
+			if let Some(metadata) = parent
+				.0
+				.children()
+				.iter()
+				.map(|c| c.1.clone())
+				.reduce(Merge::merge)
+			{
+				parent.1 = metadata
+			}
+		}
+
+		true
+	}
+
+	/// Replaces the value of the current node and returns the previous one.
+	pub fn replace_value(&mut self, value: T) -> T {
+		std::mem::replace(&mut self.node_mut().0, value)
+	}
+
+	/// Replaces the metadata of the current node and returns the previous one.
+	pub fn replace_metadata(&mut self, metadata: M) -> M {
+		std::mem::replace(&mut self.node_mut().1, metadata)
+	}
+
+	/// Modifies the metadata of the current node.
+	pub fn adjust_metadata(&mut self, f: impl FnOnce(M) -> M) {
+		let node = self.node_mut();
+		node.1 = f(node.1.clone())
+	}
+
+	/// Modifies the current node using the given function.
+	pub fn edit<U>(&mut self, f: impl FnOnce(&mut Meta<T, M>) -> U) -> U {
+		f(self.node_mut())
+	}
+
+	/// Moves the cursor back to the root, rebuilding the ancestors of the
+	/// modified nodes, and returns the edited tree.
+	///
+	/// If no node was modified, the original tree is returned borrowed.
+	pub fn finish(mut self) -> Cow<'a, Meta<T, M>> {
+		while self.up() {}
+		self.current
+	}
+}
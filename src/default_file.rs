@@ -0,0 +1,56 @@
+use crate::{Location, Origin, Span};
+
+/// File identifier type with a default file.
+///
+/// Single-file tools (such as REPLs or formatters reading the standard
+/// input) can use a file type implementing this trait to build locations
+/// without threading a file identifier everywhere.
+pub trait DefaultFile {
+	/// Returns the default file.
+	fn default_file() -> Self;
+}
+
+/// Used as file type when there is only one file.
+impl DefaultFile for () {
+	#[inline(always)]
+	fn default_file() -> Self {}
+}
+
+/// The default file is `None`.
+impl<F> DefaultFile for Option<F> {
+	#[inline(always)]
+	fn default_file() -> Self {
+		None
+	}
+}
+
+/// The default file is [`Origin::Unknown`].
+impl<F> DefaultFile for Origin<F> {
+	#[inline(always)]
+	fn default_file() -> Self {
+		Origin::Unknown
+	}
+}
+
+impl<F: DefaultFile, S> Location<F, S> {
+	/// Creates a location in the default file.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	///
+	/// let location: Location<()> = Location::of(Span::new(0, 4));
+	/// assert_eq!(location, Span::new(0, 4).located());
+	/// ```
+	#[inline(always)]
+	pub fn of(span: S) -> Self {
+		Self::new(F::default_file(), span)
+	}
+}
+
+impl Span {
+	/// Creates a location of this span in the default file.
+	#[inline(always)]
+	pub fn located<F: DefaultFile>(self) -> Location<F> {
+		Location::of(self)
+	}
+}
@@ -0,0 +1,192 @@
+use crate::render::Severity;
+use crate::Meta;
+
+/// Diagnostic item stored in a [`Diagnostics`] collection.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DiagnosticItem<E, M> {
+	/// Severity of the diagnostic.
+	pub severity: Severity,
+
+	/// Diagnostic value, with its metadata.
+	pub value: Meta<E, M>,
+}
+
+impl<E, M> DiagnosticItem<E, M> {
+	/// Creates a new diagnostic item.
+	#[inline(always)]
+	pub fn new(severity: Severity, value: Meta<E, M>) -> Self {
+		Self { severity, value }
+	}
+
+	/// Checks if the severity of this diagnostic is at least
+	/// [`Severity::Error`].
+	#[inline(always)]
+	pub fn is_error(&self) -> bool {
+		self.severity >= Severity::Error
+	}
+}
+
+/// Diagnostics accumulator.
+///
+/// Collects located diagnostics (errors, warnings, etc.) during a
+/// compilation pass, in the order they are reported.
+///
+/// ```
+/// use locspan::{Diagnostics, Meta, Span};
+///
+/// let mut diagnostics = Diagnostics::new();
+/// diagnostics.warning(Meta("unused variable", Span::new(8, 9)));
+/// assert!(!diagnostics.has_errors());
+///
+/// diagnostics.error(Meta("undefined variable", Span::new(0, 1)));
+/// assert!(diagnostics.has_errors());
+///
+/// let messages: Vec<_> = diagnostics.iter_sorted().map(|d| *d.value.value()).collect();
+/// assert_eq!(messages, ["undefined variable", "unused variable"]);
+///
+/// assert!(diagnostics.into_result().is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Diagnostics<E, M> {
+	items: Vec<DiagnosticItem<E, M>>,
+}
+
+impl<E, M> Default for Diagnostics<E, M> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self { items: Vec::new() }
+	}
+}
+
+impl<E, M> Diagnostics<E, M> {
+	/// Creates a new empty collection.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of diagnostics.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Checks if there are no diagnostics.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// Adds a diagnostic with the given severity.
+	#[inline(always)]
+	pub fn push(&mut self, severity: Severity, value: Meta<E, M>) {
+		self.items.push(DiagnosticItem::new(severity, value))
+	}
+
+	/// Adds an error.
+	#[inline(always)]
+	pub fn error(&mut self, value: Meta<E, M>) {
+		self.push(Severity::Error, value)
+	}
+
+	/// Adds a warning.
+	#[inline(always)]
+	pub fn warning(&mut self, value: Meta<E, M>) {
+		self.push(Severity::Warning, value)
+	}
+
+	/// Moves all the diagnostics of `other` into `self`.
+	#[inline(always)]
+	pub fn append(&mut self, other: &mut Self) {
+		self.items.append(&mut other.items)
+	}
+
+	/// Checks if there is at least one diagnostic with severity
+	/// [`Severity::Error`] or above.
+	#[inline(always)]
+	pub fn has_errors(&self) -> bool {
+		self.items.iter().any(DiagnosticItem::is_error)
+	}
+
+	/// Returns an iterator over the diagnostics, in the order they were
+	/// reported.
+	#[inline(always)]
+	pub fn iter(&self) -> std::slice::Iter<'_, DiagnosticItem<E, M>> {
+		self.items.iter()
+	}
+
+	/// Returns an iterator over the diagnostics, sorted by metadata.
+	///
+	/// Diagnostics with equal metadata are kept in the order they were
+	/// reported.
+	pub fn iter_sorted(&self) -> std::vec::IntoIter<&DiagnosticItem<E, M>>
+	where
+		M: Ord,
+	{
+		let mut items: Vec<_> = self.items.iter().collect();
+		items.sort_by(|a, b| a.value.metadata().cmp(b.value.metadata()));
+		items.into_iter()
+	}
+
+	/// Sorts the diagnostics by metadata.
+	///
+	/// Diagnostics with equal metadata are kept in the order they were
+	/// reported.
+	pub fn sort_by_metadata(&mut self)
+	where
+		M: Ord,
+	{
+		self.items
+			.sort_by(|a, b| a.value.metadata().cmp(b.value.metadata()))
+	}
+
+	/// Returns `Ok(())` if there are no errors, or the diagnostics
+	/// otherwise.
+	///
+	/// Diagnostics less severe than errors (such as warnings) are discarded
+	/// when there are no errors.
+	#[inline(always)]
+	pub fn into_result(self) -> Result<(), Self> {
+		if self.has_errors() {
+			Err(self)
+		} else {
+			Ok(())
+		}
+	}
+}
+
+impl<E, M> Extend<DiagnosticItem<E, M>> for Diagnostics<E, M> {
+	#[inline(always)]
+	fn extend<I: IntoIterator<Item = DiagnosticItem<E, M>>>(&mut self, iter: I) {
+		self.items.extend(iter)
+	}
+}
+
+impl<E, M> FromIterator<DiagnosticItem<E, M>> for Diagnostics<E, M> {
+	#[inline(always)]
+	fn from_iter<I: IntoIterator<Item = DiagnosticItem<E, M>>>(iter: I) -> Self {
+		Self {
+			items: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<E, M> IntoIterator for Diagnostics<E, M> {
+	type Item = DiagnosticItem<E, M>;
+	type IntoIter = std::vec::IntoIter<DiagnosticItem<E, M>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a, E, M> IntoIterator for &'a Diagnostics<E, M> {
+	type Item = &'a DiagnosticItem<E, M>;
+	type IntoIter = std::slice::Iter<'a, DiagnosticItem<E, M>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
@@ -0,0 +1,108 @@
+use crate::{Location, Span};
+
+/// Text edit.
+///
+/// Replaces the text in `span` by `new_len` bytes of new text.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Edit {
+	/// Span of the replaced text, before the edit.
+	pub span: Span,
+
+	/// Length of the new text.
+	pub new_len: usize,
+}
+
+impl Edit {
+	/// Creates a new edit replacing the text in `span` by `new_len` bytes.
+	#[inline(always)]
+	pub fn new(span: Span, new_len: usize) -> Self {
+		Self { span, new_len }
+	}
+
+	/// Creates an edit inserting `len` bytes at `offset`.
+	#[inline(always)]
+	pub fn insertion(offset: usize, len: usize) -> Self {
+		Self::new(Span::new(offset, offset), len)
+	}
+
+	/// Creates an edit deleting the text in `span`.
+	#[inline(always)]
+	pub fn deletion(span: Span) -> Self {
+		Self::new(span, 0)
+	}
+
+	/// Returns the span of the new text, after the edit.
+	#[inline(always)]
+	pub fn new_span(&self) -> Span {
+		Span::with_len(self.span.start(), self.new_len)
+	}
+
+	/// Returns the length difference introduced by this edit.
+	#[inline(always)]
+	pub fn delta(&self) -> isize {
+		self.new_len as isize - self.span.len() as isize
+	}
+
+	/// Maps a span of the text before the edit into the text after the edit.
+	///
+	/// Spans entirely before the edit are left unchanged, spans entirely
+	/// after the edit are shifted, and spans enclosing the edit are resized.
+	/// Returns `None` if the span partially overlaps the replaced text, in
+	/// which case there is no sensible way to map it.
+	///
+	/// An insertion at the end of a span is not included in the span, but
+	/// an insertion at the start of a span shifts it.
+	pub fn apply(&self, span: Span) -> Option<Span> {
+		if span.end() <= self.span.start() {
+			Some(span)
+		} else if span.start() >= self.span.end() {
+			Some(Span::new(
+				span.start() - self.span.end() + self.new_span().end(),
+				span.end() - self.span.end() + self.new_span().end(),
+			))
+		} else if span.start() <= self.span.start() && self.span.end() <= span.end() {
+			Some(Span::new(
+				span.start(),
+				span.end() - self.span.end() + self.new_span().end(),
+			))
+		} else {
+			None
+		}
+	}
+}
+
+impl Span {
+	/// Maps this span across the given batch of edits.
+	///
+	/// The edits are applied in order, each edit span being expressed in the
+	/// text obtained after applying the previous edits.
+	/// Returns `None` if the span is invalidated by one of the edits.
+	/// See [`Edit::apply`] for more details.
+	///
+	/// ```
+	/// use locspan::{Edit, Span};
+	///
+	/// let edits = [Edit::insertion(0, 2), Edit::new(Span::new(6, 8), 5)];
+	/// assert_eq!(Span::new(1, 3).remap(&edits), Some(Span::new(3, 5)));
+	/// assert_eq!(Span::new(4, 9).remap(&edits), Some(Span::new(6, 14)));
+	/// assert_eq!(Span::new(4, 5).remap(&edits), None);
+	/// ```
+	pub fn remap<'e>(self, edits: impl IntoIterator<Item = &'e Edit>) -> Option<Self> {
+		edits
+			.into_iter()
+			.try_fold(self, |span, edit| edit.apply(span))
+	}
+}
+
+impl<F> Location<F> {
+	/// Maps the span of this location across the given batch of edits applied
+	/// to its file.
+	///
+	/// Returns `None` if the location is invalidated by one of the edits.
+	/// See [`Span::remap`] for more details.
+	#[inline(always)]
+	pub fn remap<'e>(self, edits: impl IntoIterator<Item = &'e Edit>) -> Option<Self> {
+		let (file, span) = self.into_parts();
+		span.remap(edits).map(|span| Self::new(file, span))
+	}
+}
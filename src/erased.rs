@@ -0,0 +1,49 @@
+use crate::{Location, Span};
+use std::fmt;
+use std::sync::Arc;
+
+/// Type-erased file identifier.
+///
+/// Any displayable file identifier can be erased into this type, so that
+/// file-type-agnostic code (such as library error types) does not need to
+/// be generic over the file type.
+#[derive(Clone)]
+pub struct ErasedFile(Arc<dyn fmt::Display + Send + Sync>);
+
+impl ErasedFile {
+	/// Erases the given file identifier.
+	#[inline(always)]
+	pub fn new(file: impl fmt::Display + Send + Sync + 'static) -> Self {
+		Self(Arc::new(file))
+	}
+}
+
+impl fmt::Display for ErasedFile {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl fmt::Debug for ErasedFile {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ErasedFile({})", self.0)
+	}
+}
+
+/// Location with a type-erased file identifier.
+pub type ErasedLocation<S = Span> = Location<ErasedFile, S>;
+
+impl<F: fmt::Display + Send + Sync + 'static, S> Location<F, S> {
+	/// Erases the type of the file identifier.
+	///
+	/// ```
+	/// use locspan::{ErasedLocation, Location, Span};
+	///
+	/// let location: ErasedLocation = Location::new(42u32, Span::new(0, 4)).erase();
+	/// assert_eq!(location.to_string(), "42:0..4");
+	/// ```
+	#[inline(always)]
+	pub fn erase(self) -> ErasedLocation<S> {
+		self.map_file(ErasedFile::new)
+	}
+}
@@ -0,0 +1,112 @@
+use crate::Location;
+use std::path::{Component, Path, PathBuf};
+
+/// File equality relation.
+///
+/// Used to compare the files of locations when the `PartialEq`
+/// implementation of the file type is not appropriate, for instance to
+/// consider different paths to the same file as equal.
+///
+/// Any function `Fn(&F, &F) -> bool` is a file equality relation.
+pub trait FileEq<F: ?Sized> {
+	/// Checks if `a` and `b` refer to the same file.
+	fn file_eq(&self, a: &F, b: &F) -> bool;
+}
+
+impl<F: ?Sized, E: Fn(&F, &F) -> bool> FileEq<F> for E {
+	#[inline(always)]
+	fn file_eq(&self, a: &F, b: &F) -> bool {
+		self(a, b)
+	}
+}
+
+/// File equality using the `PartialEq` implementation of the file type.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct ExactFileEq;
+
+impl<F: ?Sized + PartialEq> FileEq<F> for ExactFileEq {
+	#[inline(always)]
+	fn file_eq(&self, a: &F, b: &F) -> bool {
+		a == b
+	}
+}
+
+/// Path equality after lexical normalization.
+///
+/// Paths are compared after removing `.` components and resolving `..`
+/// components lexically, without accessing the file system. For instance
+/// `./src/a.rs`, `src/a.rs` and `src/../src/a.rs` are equal.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct NormalizedPathEq;
+
+impl<F: ?Sized + AsRef<Path>> FileEq<F> for NormalizedPathEq {
+	#[inline(always)]
+	fn file_eq(&self, a: &F, b: &F) -> bool {
+		normalize(a.as_ref()) == normalize(b.as_ref())
+	}
+}
+
+/// Path equality after canonicalization.
+///
+/// Paths are compared after canonicalization using
+/// [`std::fs::canonicalize`], resolving symbolic links and relative paths
+/// against the current directory. Paths that cannot be canonicalized (e.g.
+/// because the file does not exist) are compared after lexical
+/// normalization, as in [`NormalizedPathEq`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct CanonicalPathEq;
+
+impl<F: ?Sized + AsRef<Path>> FileEq<F> for CanonicalPathEq {
+	fn file_eq(&self, a: &F, b: &F) -> bool {
+		let canonicalize = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| normalize(p));
+		canonicalize(a.as_ref()) == canonicalize(b.as_ref())
+	}
+}
+
+/// Lexically normalizes the given path.
+fn normalize(path: &Path) -> PathBuf {
+	let mut result = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => (),
+			Component::ParentDir => match result.components().next_back() {
+				Some(Component::Normal(_)) => {
+					result.pop();
+				}
+				Some(Component::RootDir | Component::Prefix(_)) => (),
+				_ => result.push(component),
+			},
+			component => result.push(component),
+		}
+	}
+	result
+}
+
+impl<F, S> Location<F, S> {
+	/// Checks if the two locations are in the same file, using the given
+	/// file equality relation.
+	///
+	/// ```
+	/// use locspan::{Location, NormalizedPathEq, Span};
+	/// use std::path::Path;
+	///
+	/// let a = Location::new(Path::new("./src/a.rs"), Span::new(0, 1));
+	/// let b = Location::new(Path::new("src/a.rs"), Span::new(4, 5));
+	/// assert!(!a.same_file(&b));
+	/// assert!(a.same_file_with(&b, &NormalizedPathEq));
+	/// ```
+	#[inline(always)]
+	pub fn same_file_with<T>(&self, other: &Location<F, T>, eq: &impl FileEq<F>) -> bool {
+		eq.file_eq(self.file(), other.file())
+	}
+
+	/// Checks if the two locations are equal, using the given file equality
+	/// relation.
+	#[inline(always)]
+	pub fn eq_with(&self, other: &Self, eq: &impl FileEq<F>) -> bool
+	where
+		S: PartialEq,
+	{
+		self.span_ref() == other.span_ref() && self.same_file_with(other, eq)
+	}
+}
@@ -0,0 +1,215 @@
+use crate::Location;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::num::NonZeroU32;
+use std::ops::Index;
+
+/// Interned file identifier.
+///
+/// Created by a [`FileRegistry`].
+/// Contrarily to most file identifiers (such as paths), it is small and
+/// `Copy`, and so is `Location<FileId>`.
+///
+/// ## Layout
+///
+/// A `FileId` is a `NonZeroU32`, so it is guaranteed to be 4 bytes long
+/// and `Option<FileId>` has the same size as `FileId`.
+/// In turn, the niche is propagated so that `Option<Location<FileId>>` has
+/// the same size as `Location<FileId>`, which is 24 bytes on 64-bit
+/// platforms.
+///
+/// ```
+/// use locspan::{FileId, Location};
+/// use std::mem::size_of;
+///
+/// assert_eq!(size_of::<FileId>(), 4);
+/// assert_eq!(size_of::<Option<FileId>>(), 4);
+/// assert_eq!(size_of::<Option<Location<FileId>>>(), size_of::<Location<FileId>>());
+///
+/// #[cfg(target_pointer_width = "64")]
+/// assert_eq!(size_of::<Location<FileId>>(), 24);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+#[repr(transparent)]
+pub struct FileId(NonZeroU32);
+
+const _: () = assert!(std::mem::size_of::<Option<FileId>>() == std::mem::size_of::<FileId>());
+const _: () = assert!(
+	std::mem::size_of::<Option<Location<FileId>>>() == std::mem::size_of::<Location<FileId>>()
+);
+
+impl FileId {
+	/// Returns the index of the file in its registry.
+	///
+	/// Files are numbered in insertion order, starting from 0.
+	#[inline(always)]
+	pub fn index(&self) -> usize {
+		(self.0.get() - 1) as usize
+	}
+
+	/// Creates a file id from its index, if it fits.
+	#[inline(always)]
+	pub(crate) fn from_index(index: usize) -> Option<Self> {
+		let n = u32::try_from(index).ok()?.checked_add(1)?;
+		NonZeroU32::new(n).map(Self)
+	}
+}
+
+/// File identifiers interning registry.
+///
+/// Maps file identifiers (paths, IRIs, etc.) of type `F` to small [`FileId`]s
+/// and back. Ids are stable: inserting new files never changes the id of the
+/// already registered ones.
+///
+/// ```
+/// use locspan::{FileRegistry, Location, Span};
+///
+/// let mut files = FileRegistry::new();
+/// let main = files.insert("src/main.rs".to_string());
+/// let lib = files.insert("src/lib.rs".to_string());
+/// assert_eq!(files.insert("src/main.rs".to_string()), main);
+/// assert_eq!(files.get("src/lib.rs"), Some(lib));
+///
+/// let location = Location::new(lib, Span::new(0, 4));
+/// assert_eq!(files.resolve(location).file().as_str(), "src/lib.rs");
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileRegistry<F> {
+	/// Registered files, indexed by id.
+	files: Vec<F>,
+
+	/// Map from files to their id.
+	ids: HashMap<F, FileId>,
+}
+
+impl<F> Default for FileRegistry<F> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self {
+			files: Vec::new(),
+			ids: HashMap::new(),
+		}
+	}
+}
+
+impl<F> FileRegistry<F> {
+	/// Creates a new empty registry.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of registered files.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.files.len()
+	}
+
+	/// Checks if the registry is empty.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.files.is_empty()
+	}
+
+	/// Returns the file with the given id, if it is registered.
+	#[inline(always)]
+	pub fn file(&self, id: FileId) -> Option<&F> {
+		self.files.get(id.index())
+	}
+
+	/// Replaces the file identifier of the given location with a reference
+	/// to the registered file.
+	///
+	/// ## Panics
+	///
+	/// Panics if the file is not registered.
+	#[inline(always)]
+	pub fn resolve<S>(&self, location: Location<FileId, S>) -> Location<&F, S> {
+		location.map_file(|id| &self[id])
+	}
+
+	/// Returns an iterator over the registered files with their id, in
+	/// insertion order.
+	#[inline(always)]
+	pub fn iter(&self) -> RegisteredFiles<'_, F> {
+		RegisteredFiles(self.files.iter().enumerate())
+	}
+}
+
+impl<F: Eq + Hash> FileRegistry<F> {
+	/// Returns the id of the given file, if it is registered.
+	#[inline(always)]
+	pub fn get<Q: ?Sized + Eq + Hash>(&self, file: &Q) -> Option<FileId>
+	where
+		F: Borrow<Q>,
+	{
+		self.ids.get(file).copied()
+	}
+
+	/// Checks if the given file is registered.
+	#[inline(always)]
+	pub fn contains<Q: ?Sized + Eq + Hash>(&self, file: &Q) -> bool
+	where
+		F: Borrow<Q>,
+	{
+		self.ids.contains_key(file)
+	}
+
+	/// Registers the given file, if it is not already, and returns its id.
+	///
+	/// ## Panics
+	///
+	/// Panics if the number of registered files overflows `u32`.
+	pub fn insert(&mut self, file: F) -> FileId
+	where
+		F: Clone,
+	{
+		match self.ids.get(&file) {
+			Some(id) => *id,
+			None => {
+				let id = FileId::from_index(self.files.len()).expect("too many files");
+				self.files.push(file.clone());
+				self.ids.insert(file, id);
+				id
+			}
+		}
+	}
+}
+
+impl<F> Index<FileId> for FileRegistry<F> {
+	type Output = F;
+
+	fn index(&self, id: FileId) -> &F {
+		&self.files[id.index()]
+	}
+}
+
+impl<'a, F> IntoIterator for &'a FileRegistry<F> {
+	type Item = (FileId, &'a F);
+	type IntoIter = RegisteredFiles<'a, F>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Iterator over the files of a [`FileRegistry`] with their id.
+pub struct RegisteredFiles<'a, F>(std::iter::Enumerate<std::slice::Iter<'a, F>>);
+
+impl<'a, F> Iterator for RegisteredFiles<'a, F> {
+	type Item = (FileId, &'a F);
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0
+			.next()
+			.map(|(i, f)| (FileId::from_index(i).unwrap(), f))
+	}
+}
+
+impl<F> ExactSizeIterator for RegisteredFiles<'_, F> {}
@@ -0,0 +1,83 @@
+//! Builder-style API to construct annotated values in tests.
+//!
+//! Writing the expected output of a parser requires annotating every node
+//! with its metadata. This module provides helpers to compute the metadata of
+//! parent nodes from the metadata of their children using the [`Merge`]
+//! trait, so only the leaves must be given an explicit span.
+//!
+//! ```
+//! use locspan::Span;
+//! use locspan::fixture::{node, seq};
+//!
+//! let list = seq([node('a').spanning(1..2), node('b').spanning(4..5)]).auto_span();
+//! assert_eq!(list.1, Span::new(1, 5));
+//!
+//! let call = node(("f", list)).covering([Span::new(0, 1), Span::new(0, 6)]);
+//! assert_eq!(call.1, Span::new(0, 6));
+//! ```
+use crate::{Merge, Meta, Span};
+
+/// Node under construction.
+pub struct Node<T>(T);
+
+/// Starts building a node with the given value.
+#[inline(always)]
+pub fn node<T>(value: T) -> Node<T> {
+	Node(value)
+}
+
+impl<T> Node<T> {
+	/// Annotates the node with the given span.
+	#[inline(always)]
+	pub fn spanning(self, span: impl Into<Span>) -> Meta<T, Span> {
+		Meta(self.0, span.into())
+	}
+
+	/// Annotates the node with the merge of the given metadata.
+	///
+	/// ## Panics
+	///
+	/// Panics if `metadata` is empty.
+	#[inline(always)]
+	pub fn covering<M: Merge>(self, metadata: impl IntoIterator<Item = M>) -> Meta<T, M> {
+		Meta(self.0, merge_all(metadata))
+	}
+}
+
+/// Sequence of annotated nodes under construction.
+pub struct Seq<T, M>(Vec<Meta<T, M>>);
+
+/// Starts building a sequence of annotated nodes.
+#[inline(always)]
+pub fn seq<T, M>(items: impl IntoIterator<Item = Meta<T, M>>) -> Seq<T, M> {
+	Seq(items.into_iter().collect())
+}
+
+impl<T, M> Seq<T, M> {
+	/// Annotates the sequence with the given span.
+	#[inline(always)]
+	pub fn spanning(self, span: impl Into<Span>) -> Meta<Vec<Meta<T, M>>, Span> {
+		Meta(self.0, span.into())
+	}
+
+	/// Annotates the sequence with the merge of its items' metadata.
+	///
+	/// ## Panics
+	///
+	/// Panics if the sequence is empty.
+	#[inline(always)]
+	pub fn auto_span(self) -> Meta<Vec<Meta<T, M>>, M>
+	where
+		M: Clone + Merge,
+	{
+		let metadata = merge_all(self.0.iter().map(|item| item.1.clone()));
+		Meta(self.0, metadata)
+	}
+}
+
+fn merge_all<M: Merge>(metadata: impl IntoIterator<Item = M>) -> M {
+	metadata
+		.into_iter()
+		.reduce(M::merge)
+		.expect("cannot compute the metadata of a node without children")
+}
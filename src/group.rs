@@ -0,0 +1,82 @@
+use crate::{Loc, Location, Meta, WithMetadata};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Groups the given locations by file.
+///
+/// The spans of each file are sorted.
+///
+/// ```
+/// use locspan::{group_by_file, Location, Span};
+///
+/// let groups = group_by_file([
+///   Location::new("b.rs", Span::new(4, 5)),
+///   Location::new("a.rs", Span::new(0, 1)),
+///   Location::new("b.rs", Span::new(1, 2)),
+/// ]);
+///
+/// assert_eq!(groups["b.rs"], [Span::new(1, 2), Span::new(4, 5)]);
+/// ```
+pub fn group_by_file<F: Eq + Hash, S: Ord>(
+	locations: impl IntoIterator<Item = Location<F, S>>,
+) -> HashMap<F, Vec<S>> {
+	let mut groups: HashMap<F, Vec<S>> = HashMap::new();
+	for location in locations {
+		let (file, span) = location.into_parts();
+		groups.entry(file).or_default().push(span)
+	}
+
+	for spans in groups.values_mut() {
+		spans.sort()
+	}
+
+	groups
+}
+
+/// Groups the given located values by file, ordering files.
+///
+/// The values of each file are stably sorted by span.
+pub fn group_values_by_file<T, F: Ord, S: Ord>(
+	values: impl IntoIterator<Item = Loc<T, F, S>>,
+) -> BTreeMap<F, Vec<Meta<T, S>>> {
+	let mut groups: BTreeMap<F, Vec<Meta<T, S>>> = BTreeMap::new();
+	for Meta(value, location) in values {
+		let (file, span) = location.into_parts();
+		groups.entry(file).or_default().push(Meta(value, span))
+	}
+
+	for values in groups.values_mut() {
+		values.sort_by(|a, b| a.1.cmp(&b.1))
+	}
+
+	groups
+}
+
+/// Groups the given values by metadata, ordering the metadata.
+///
+/// The values of each group are kept in their original order.
+///
+/// ```
+/// use locspan::{group_by_metadata, Meta};
+///
+/// let groups = group_by_metadata([Meta('b', "y.rs"), Meta('a', "x.rs"), Meta('c', "y.rs")]);
+/// let files: Vec<_> = groups.keys().copied().collect();
+/// assert_eq!(files, ["x.rs", "y.rs"]);
+/// assert_eq!(groups["y.rs"], [Meta('b', "y.rs"), Meta('c', "y.rs")]);
+/// ```
+pub fn group_by_metadata<T: WithMetadata>(
+	values: impl IntoIterator<Item = T>,
+) -> BTreeMap<T::Metadata, Vec<T>>
+where
+	T::Metadata: Ord + Clone,
+{
+	let mut groups: BTreeMap<T::Metadata, Vec<T>> = BTreeMap::new();
+	for value in values {
+		groups
+			.entry(value.metadata().clone())
+			.or_default()
+			.push(value)
+	}
+
+	groups
+}
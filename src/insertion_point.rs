@@ -0,0 +1,136 @@
+use crate::{Edit, Span, Spanned};
+
+/// Side to which an insertion point is attached.
+///
+/// An empty span is ambiguous between "end of the previous token" and
+/// "start of the next token". The affinity disambiguates the two.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Affinity {
+	/// Attached to the text before the insertion point (end of the previous
+	/// token).
+	Backward,
+
+	/// Attached to the text after the insertion point (start of the next
+	/// token).
+	#[default]
+	Forward,
+}
+
+/// Zero-width position between two bytes, attached to one side.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct InsertionPoint {
+	/// Byte position.
+	offset: usize,
+
+	/// Side to which the position is attached.
+	affinity: Affinity,
+}
+
+impl InsertionPoint {
+	/// Creates a new insertion point at the given byte position.
+	#[inline(always)]
+	pub fn new(offset: usize, affinity: Affinity) -> Self {
+		Self { offset, affinity }
+	}
+
+	/// Returns the byte position of the insertion point.
+	#[inline(always)]
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Returns the side to which the insertion point is attached.
+	#[inline(always)]
+	pub fn affinity(&self) -> Affinity {
+		self.affinity
+	}
+
+	/// Sets the side to which the insertion point is attached.
+	#[inline(always)]
+	pub fn set_affinity(&mut self, affinity: Affinity) {
+		self.affinity = affinity
+	}
+
+	/// Returns the empty span at the insertion point.
+	#[inline(always)]
+	pub fn span(&self) -> Span {
+		self.offset.into()
+	}
+
+	/// Maps the insertion point across an edit.
+	///
+	/// Text inserted exactly at the insertion point is placed after it if
+	/// its affinity is [`Affinity::Backward`], and before it if its affinity
+	/// is [`Affinity::Forward`].
+	/// Returns `None` if the insertion point is strictly inside the replaced
+	/// text.
+	pub fn apply(&self, edit: &Edit) -> Option<Self> {
+		let offset = if self.offset < edit.span.start()
+			|| (self.offset == edit.span.start() && self.affinity == Affinity::Backward)
+		{
+			self.offset
+		} else if self.offset > edit.span.end()
+			|| (self.offset == edit.span.end() && self.affinity == Affinity::Forward)
+		{
+			self.offset - edit.span.end() + edit.new_span().end()
+		} else if self.offset == edit.span.start() {
+			edit.span.start()
+		} else if self.offset == edit.span.end() {
+			edit.new_span().end()
+		} else {
+			return None;
+		};
+
+		Some(Self::new(offset, self.affinity))
+	}
+
+	/// Maps the insertion point across the given batch of edits.
+	///
+	/// See [`Span::remap`] for the interpretation of the batch.
+	pub fn remap<'e>(self, edits: impl IntoIterator<Item = &'e Edit>) -> Option<Self> {
+		edits
+			.into_iter()
+			.try_fold(self, |point, edit| point.apply(edit))
+	}
+}
+
+impl Span {
+	/// Returns the insertion point just before the span, attached to it.
+	///
+	/// ```
+	/// use locspan::{Affinity, Edit, Span};
+	///
+	/// let span = Span::new(4, 8);
+	/// let before = span.caret_before();
+	/// let after = span.caret_after();
+	///
+	/// let edits = [Edit::insertion(4, 2), Edit::insertion(10, 1)];
+	/// assert_eq!(before.remap(&edits).unwrap().offset(), 6);
+	/// assert_eq!(after.remap(&edits).unwrap().offset(), 10);
+	/// ```
+	#[inline(always)]
+	pub fn caret_before(&self) -> InsertionPoint {
+		InsertionPoint::new(self.start(), Affinity::Forward)
+	}
+
+	/// Returns the insertion point just after the span, attached to it.
+	#[inline(always)]
+	pub fn caret_after(&self) -> InsertionPoint {
+		InsertionPoint::new(self.end(), Affinity::Backward)
+	}
+}
+
+impl From<InsertionPoint> for Span {
+	#[inline(always)]
+	fn from(point: InsertionPoint) -> Self {
+		point.span()
+	}
+}
+
+impl Spanned for InsertionPoint {
+	type Span = Span;
+
+	fn span(&self) -> Self::Span {
+		self.offset.into()
+	}
+}
@@ -4,24 +4,123 @@
 //! - `Location<F>` combines a `Span` with a file identifier `F` to pin point a syntactic element in a source file.
 //! - `Meta<T, M>` wraps any value `T` with some metadata of type `M`.
 //! - `Loc<T, F, S> = Meta<T, Location<F, S>>` wraps any value `T` and with its location.
+//! - `SpannedValue<T> = Meta<T, Span>` wraps any value `T` with its span, for single-file tools.
 //!
 //! Extra traits are also provided to extend common types (`Option`, `Result`, etc.) with localization functions.
 //! The crate integrates well with diagnostic reporting libraries such as
 //! [`codespan-reporting`](https://crates.io/crates/codespan-reporting) to render beautiful error reports.
+mod by_metadata;
+mod char_spans;
+mod compact_span;
+mod cursor;
+mod default_file;
+mod diagnostics;
+mod edit;
+mod erased;
+mod file_eq;
+mod file_registry;
+pub mod fixture;
+mod group;
+mod insertion_point;
+mod line_col;
+mod line_ending;
 mod loc;
+mod located_error;
 mod location;
+mod location_stack;
+mod matches;
+mod merge;
 mod meta;
+mod meta_iter;
+mod meta_ops;
+mod origin;
+mod path;
+mod relex;
+pub mod render;
+mod source;
+mod source_id;
 mod span;
+mod span_tracker;
+mod spanned_str;
+mod spanned_value;
+mod stable_hash;
 mod strip;
+mod tree_path;
+mod versioned;
+mod with_metadata;
 
 #[cfg(feature = "reporting")]
 mod reporting;
 
+#[cfg(feature = "codespan")]
+mod codespan;
+
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(feature = "proc-macro")]
+mod proc_macro;
+
+#[cfg(feature = "lsp")]
+mod lsp;
+
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+
+#[cfg(feature = "termcolor")]
+mod termcolor;
+
+#[cfg(feature = "text-size")]
+mod text_size;
+
+#[cfg(feature = "unicode-segmentation")]
+mod unicode_segmentation;
+
+pub use by_metadata::*;
+pub use char_spans::*;
+pub use compact_span::*;
+pub use cursor::*;
+pub use default_file::*;
+pub use diagnostics::*;
+pub use edit::*;
+pub use erased::*;
+pub use file_eq::*;
+pub use file_registry::*;
+pub use group::*;
+pub use insertion_point::*;
+pub use line_col::*;
+pub use line_ending::*;
 pub use loc::*;
+pub use located_error::*;
 pub use location::*;
+pub use location_stack::*;
+pub use matches::*;
+pub use merge::*;
 pub use meta::*;
+pub use meta_iter::*;
+pub use origin::*;
+pub use path::*;
+pub use relex::*;
+pub use source::*;
+pub use source_id::*;
 pub use span::*;
+pub use span_tracker::*;
+pub use spanned_str::*;
+pub use spanned_value::*;
+pub use stable_hash::*;
 pub use strip::*;
+pub use tree_path::*;
+pub use versioned::*;
+pub use with_metadata::*;
+
+#[cfg(feature = "reporting")]
+pub use reporting::*;
+
+#[cfg(feature = "serde")]
+pub use self::serde::*;
+
+#[cfg(feature = "derive")]
+pub use locspan_derive::*;
@@ -0,0 +1,365 @@
+use crate::{Location, Span};
+use std::fmt;
+
+/// Line and column position in a source file.
+///
+/// Both the line and column numbers start from 0.
+/// The column is given in bytes from the start of the line, unless another
+/// [`ColumnEncoding`] is specified.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Position {
+	/// Line number, starting from 0.
+	pub line: usize,
+
+	/// Column number, starting from 0.
+	pub column: usize,
+}
+
+impl Position {
+	/// Creates a new position.
+	#[inline(always)]
+	pub fn new(line: usize, column: usize) -> Self {
+		Self { line, column }
+	}
+}
+
+/// Range of a source file given by line and column positions.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct LineColSpan {
+	/// Start position (included).
+	pub start: Position,
+
+	/// End position (excluded).
+	pub end: Position,
+}
+
+impl LineColSpan {
+	/// Creates a new line/column span.
+	#[inline(always)]
+	pub fn new(start: Position, end: Position) -> Self {
+		Self { start, end }
+	}
+}
+
+/// Encoding used to count columns.
+///
+/// Columns can be counted in bytes (UTF-8 code units), in UTF-16 code units
+/// (as required by the Language Server Protocol) or in characters (Unicode
+/// scalar values, or UTF-32 code units).
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum ColumnEncoding {
+	/// Columns are counted in bytes.
+	#[default]
+	Utf8,
+
+	/// Columns are counted in UTF-16 code units.
+	Utf16,
+
+	/// Columns are counted in characters.
+	Utf32,
+}
+
+impl ColumnEncoding {
+	/// Returns the number of code units of the given character in this encoding.
+	#[inline(always)]
+	pub fn char_len(&self, c: char) -> usize {
+		match self {
+			Self::Utf8 => c.len_utf8(),
+			Self::Utf16 => c.len_utf16(),
+			Self::Utf32 => 1,
+		}
+	}
+
+	/// Computes the column, in this encoding, of the given byte `offset` in a
+	/// `line` of text.
+	///
+	/// Only the characters fully preceding `offset` are counted.
+	pub fn column(&self, line: &str, offset: usize) -> usize {
+		match self {
+			Self::Utf8 => offset,
+			_ => line
+				.char_indices()
+				.take_while(|(i, c)| i + c.len_utf8() <= offset)
+				.map(|(_, c)| self.char_len(c))
+				.sum(),
+		}
+	}
+
+	/// Computes the byte offset of the given `column`, in this encoding, in a
+	/// `line` of text.
+	///
+	/// Returns `None` if the column is past the end of the line, or in the
+	/// middle of a character.
+	pub fn offset(&self, line: &str, column: usize) -> Option<usize> {
+		let mut current = 0;
+		for (i, c) in line.char_indices() {
+			if current == column {
+				return Some(i);
+			}
+
+			current += self.char_len(c);
+			if current > column {
+				return None;
+			}
+		}
+
+		if current == column {
+			Some(line.len())
+		} else {
+			None
+		}
+	}
+}
+
+/// Index of the lines of a source text.
+///
+/// Provides conversions between byte offsets and line/column positions.
+#[derive(Clone, Debug)]
+pub struct LineIndex<'a> {
+	/// Source text.
+	source: &'a str,
+
+	/// Offset of the first byte of each line.
+	line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+	/// Indexes the lines of the given `source` text.
+	pub fn new(source: &'a str) -> Self {
+		let mut line_starts = vec![0];
+		line_starts.extend(
+			source
+				.bytes()
+				.enumerate()
+				.filter(|(_, b)| *b == b'\n')
+				.map(|(i, _)| i + 1),
+		);
+
+		Self {
+			source,
+			line_starts,
+		}
+	}
+
+	/// Returns the indexed source text.
+	#[inline(always)]
+	pub fn source(&self) -> &'a str {
+		self.source
+	}
+
+	/// Returns the number of lines in the source text.
+	#[inline(always)]
+	pub fn line_count(&self) -> usize {
+		self.line_starts.len()
+	}
+
+	/// Returns the span of the given line, without its line break.
+	pub fn line_span(&self, line: usize) -> Option<Span> {
+		let start = *self.line_starts.get(line)?;
+		let end = match self.line_starts.get(line + 1) {
+			Some(next) => {
+				let end = next - 1;
+				if end > start && self.source.as_bytes()[end - 1] == b'\r' {
+					end - 1
+				} else {
+					end
+				}
+			}
+			None => self.source.len(),
+		};
+
+		Some(Span::new(start, end))
+	}
+
+	/// Returns the text of the given line, without its line break.
+	#[inline(always)]
+	pub fn line(&self, line: usize) -> Option<&'a str> {
+		self.line_span(line).map(|span| &self.source[span])
+	}
+
+	/// Returns the line containing the given byte offset.
+	///
+	/// Returns `None` if the offset is past the end of the source text.
+	pub fn line_of(&self, offset: usize) -> Option<usize> {
+		if offset <= self.source.len() {
+			Some(self.line_starts.partition_point(|start| *start <= offset) - 1)
+		} else {
+			None
+		}
+	}
+
+	/// Converts a byte offset into a line/column position.
+	///
+	/// Returns `None` if the offset is past the end of the source text.
+	pub fn position(&self, offset: usize) -> Option<Position> {
+		let line = self.line_of(offset)?;
+		Some(Position::new(line, offset - self.line_starts[line]))
+	}
+
+	/// Converts a line/column position into a byte offset.
+	///
+	/// Returns `None` if the line does not exist, or if the column is past
+	/// the end of the line.
+	pub fn offset(&self, position: Position) -> Option<usize> {
+		let span = self.line_span(position.line)?;
+		if position.column <= span.len() {
+			Some(span.start() + position.column)
+		} else {
+			None
+		}
+	}
+
+	/// Converts a byte offset into a line/column position, with the column
+	/// counted in the given `encoding`.
+	///
+	/// Returns `None` if the offset is past the end of the source text.
+	pub fn position_with(&self, offset: usize, encoding: ColumnEncoding) -> Option<Position> {
+		let line = self.line_of(offset)?;
+		let start = self.line_starts[line];
+		let text = &self.source[start..];
+		Some(Position::new(line, encoding.column(text, offset - start)))
+	}
+
+	/// Converts a line/column position, with the column counted in the given
+	/// `encoding`, into a byte offset.
+	///
+	/// Returns `None` if the line does not exist, or if the column is past
+	/// the end of the line or in the middle of a character.
+	pub fn offset_with(&self, position: Position, encoding: ColumnEncoding) -> Option<usize> {
+		let span = self.line_span(position.line)?;
+		let column = encoding.offset(&self.source[span], position.column)?;
+		Some(span.start() + column)
+	}
+
+	/// Converts a byte span into a line/column span.
+	///
+	/// Returns `None` if the span is past the end of the source text.
+	#[inline(always)]
+	pub fn line_col_span(&self, span: Span) -> Option<LineColSpan> {
+		Some(LineColSpan::new(
+			self.position(span.start())?,
+			self.position(span.end())?,
+		))
+	}
+
+	/// Converts a line/column span into a byte span.
+	///
+	/// Returns `None` if one of the positions is not in the source text.
+	#[inline(always)]
+	pub fn span(&self, span: LineColSpan) -> Option<Span> {
+		Some(Span::new(self.offset(span.start)?, self.offset(span.end)?))
+	}
+
+	/// Converts a byte span into a line/column span, with columns counted in
+	/// the given `encoding`.
+	///
+	/// Returns `None` if the span is past the end of the source text.
+	#[inline(always)]
+	pub fn line_col_span_with(&self, span: Span, encoding: ColumnEncoding) -> Option<LineColSpan> {
+		Some(LineColSpan::new(
+			self.position_with(span.start(), encoding)?,
+			self.position_with(span.end(), encoding)?,
+		))
+	}
+
+	/// Converts a line/column span, with columns counted in the given
+	/// `encoding`, into a byte span.
+	///
+	/// Returns `None` if one of the positions is not in the source text.
+	#[inline(always)]
+	pub fn span_with(&self, span: LineColSpan, encoding: ColumnEncoding) -> Option<Span> {
+		Some(Span::new(
+			self.offset_with(span.start, encoding)?,
+			self.offset_with(span.end, encoding)?,
+		))
+	}
+}
+
+impl<F> Location<F> {
+	/// Creates a location in `file` from a start and end line/column
+	/// positions, using the line index of the file's source text.
+	///
+	/// Positions are 0-based, with columns counted in bytes.
+	/// Returns `None` if one of the positions is not in the source text.
+	///
+	/// ```
+	/// use locspan::{LineIndex, Location, Position, Span};
+	///
+	/// let index = LineIndex::new("fn main() {\n  a\n}");
+	/// let location = Location::from_line_col("main.rs", Position::new(1, 2), Position::new(1, 3), &index).unwrap();
+	/// assert_eq!(location, Location::new("main.rs", Span::new(14, 15)));
+	/// ```
+	#[inline(always)]
+	pub fn from_line_col(
+		file: F,
+		start: Position,
+		end: Position,
+		index: &LineIndex,
+	) -> Option<Self> {
+		let span = index.span(LineColSpan::new(start, end))?;
+		Some(Self::new(file, span))
+	}
+
+	/// Converts this location into a line/column location, using the line
+	/// index of its file.
+	///
+	/// Returns `None` if the span is past the end of the source text.
+	#[inline(always)]
+	pub fn to_line_col(&self, index: &LineIndex) -> Option<Location<F, LineColSpan>>
+	where
+		F: Clone,
+	{
+		let span = index.line_col_span(self.span())?;
+		Some(Location::new(self.file().clone(), span))
+	}
+
+	/// Returns a value displaying the location in the style of `rustc`, as
+	/// `file:line:column`, using the given line index of its file.
+	///
+	/// Lines and columns are displayed starting from 1, and columns are
+	/// counted in characters.
+	/// If the start of the span is outside of the indexed source, the location
+	/// is displayed as `file:start..end`.
+	///
+	/// ```
+	/// use locspan::{LineIndex, Location, Span};
+	///
+	/// let index = LineIndex::new("fn main() {\n  a\n}");
+	/// let location = Location::new("main.rs", Span::new(14, 15));
+	/// assert_eq!(location.to_string(), "main.rs:14..15");
+	/// assert_eq!(location.display_with(&index).to_string(), "main.rs:2:3");
+	/// ```
+	#[inline(always)]
+	pub fn display_with<'a>(&'a self, index: &'a LineIndex<'a>) -> DisplayLineCol<'a, F> {
+		DisplayLineCol {
+			location: self,
+			index,
+		}
+	}
+}
+
+/// Displays a location as `file:line:column`.
+///
+/// Created by the [`Location::display_with`] method.
+pub struct DisplayLineCol<'a, F> {
+	location: &'a Location<F>,
+	index: &'a LineIndex<'a>,
+}
+
+impl<F: fmt::Display> fmt::Display for DisplayLineCol<'_, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self
+			.index
+			.position_with(self.location.span().start(), ColumnEncoding::Utf32)
+		{
+			Some(position) => write!(
+				f,
+				"{}:{}:{}",
+				self.location.file(),
+				position.line + 1,
+				position.column + 1
+			),
+			None => self.location.fmt(f),
+		}
+	}
+}
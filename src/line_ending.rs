@@ -0,0 +1,106 @@
+use crate::Span;
+
+/// Source text with normalized line endings.
+///
+/// Every `\r\n` and lone `\r` line ending of the original text is replaced
+/// by `\n`. The offsets removed by the normalization are recorded, so that
+/// spans computed on the normalized text can be translated back to the
+/// original text, and vice versa.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NormalizedLineEndings {
+	/// Normalized text.
+	text: String,
+
+	/// Offsets, in the normalized text, of the line breaks that replaced a
+	/// `\r\n` sequence.
+	normalized: Vec<usize>,
+
+	/// Offsets, in the original text, of the `\r\n` sequences replaced by a
+	/// single line break.
+	original: Vec<usize>,
+}
+
+impl NormalizedLineEndings {
+	/// Normalizes the line endings of the given `source` text.
+	pub fn new(source: &str) -> Self {
+		let mut text = String::with_capacity(source.len());
+		let mut normalized = Vec::new();
+		let mut original = Vec::new();
+
+		let mut rest = source;
+		while let Some(i) = rest.find('\r') {
+			text.push_str(&rest[..i]);
+			let offset = source.len() - rest.len() + i;
+
+			if rest[i + 1..].starts_with('\n') {
+				normalized.push(text.len());
+				original.push(offset);
+				rest = &rest[i + 2..]
+			} else {
+				rest = &rest[i + 1..]
+			}
+
+			text.push('\n');
+		}
+		text.push_str(rest);
+
+		Self {
+			text,
+			normalized,
+			original,
+		}
+	}
+
+	/// Returns the normalized text.
+	#[inline(always)]
+	pub fn as_str(&self) -> &str {
+		&self.text
+	}
+
+	/// Consumes this value and returns the normalized text.
+	#[inline(always)]
+	pub fn into_string(self) -> String {
+		self.text
+	}
+
+	/// Checks if the normalization changed any offset.
+	#[inline(always)]
+	pub fn is_identity(&self) -> bool {
+		self.normalized.is_empty()
+	}
+
+	/// Translates an offset in the normalized text into an offset in the
+	/// original text.
+	///
+	/// The offset of a line break that replaced a `\r\n` sequence is
+	/// translated into the offset of the `\r`.
+	pub fn to_original(&self, offset: usize) -> usize {
+		offset + self.normalized.partition_point(|n| *n < offset)
+	}
+
+	/// Translates an offset in the original text into an offset in the
+	/// normalized text.
+	///
+	/// An offset pointing between the `\r` and `\n` of a line ending is
+	/// translated into the offset following the normalized line break.
+	pub fn to_normalized(&self, offset: usize) -> usize {
+		offset - self.original.partition_point(|o| o + 2 <= offset)
+	}
+
+	/// Translates a span of the normalized text into a span of the original
+	/// text.
+	#[inline(always)]
+	pub fn span_to_original(&self, span: Span) -> Span {
+		Span::new(self.to_original(span.start()), self.to_original(span.end()))
+	}
+
+	/// Translates a span of the original text into a span of the normalized
+	/// text.
+	#[inline(always)]
+	pub fn span_to_normalized(&self, span: Span) -> Span {
+		Span::new(
+			self.to_normalized(span.start()),
+			self.to_normalized(span.end()),
+		)
+	}
+}
@@ -1,4 +1,4 @@
-use crate::{Location, Meta, Span};
+use crate::{Location, MapMetadataRecursively, MaybeLocated, Meta, Span};
 
 /// Data with `Location` metadata.
 ///
@@ -14,6 +14,19 @@ pub fn Loc<T, F, S>(t: T, location: Location<F, S>) -> Loc<T, F, S> {
 	Meta(t, location)
 }
 
+/// Data with boxed `Location` metadata.
+///
+/// Boxing the location keeps the size of the value small, which is useful
+/// when the file type is large (such as `PathBuf`).
+///
+/// ```
+/// use std::path::PathBuf;
+/// use locspan::{BoxedLoc, Loc};
+///
+/// assert!(std::mem::size_of::<BoxedLoc<u64, PathBuf>>() < std::mem::size_of::<Loc<u64, PathBuf>>());
+/// ```
+pub type BoxedLoc<T, F, S = Span> = Meta<T, Box<Location<F, S>>>;
+
 impl<T, F, S> Loc<T, F, S> {
 	/// Discards the value and returns its location.
 	pub fn into_location(self) -> Location<F, S> {
@@ -119,7 +132,7 @@ impl<T, F, S> Loc<T, F, S> {
 	}
 }
 
-impl<'f, T, F: Clone, S: Clone> Loc<T, &'f F, S> {
+impl<T, F: Clone, S: Clone> Loc<T, &F, S> {
 	/// Clones the value and the borrowed file to return a new `Loc<T, F>`.
 	#[inline(always)]
 	pub fn cloned_file(&self) -> Loc<T, F, S>
@@ -136,9 +149,144 @@ impl<'f, T, F: Clone, S: Clone> Loc<T, &'f F, S> {
 	}
 }
 
-impl<'t, 'f, T: Clone, F: Clone, S: Clone> Loc<&'t T, &'f F, S> {
+impl<T: Clone, F: Clone, S: Clone> Loc<&T, &F, S> {
 	/// Clones the borrowed value and file to return a new `Loc<T, F>`.
 	pub fn cloned(&self) -> Loc<T, F, S> {
 		Loc(self.0.clone(), self.1.cloned())
 	}
 }
+
+/// Data with optional `Location` metadata.
+///
+/// This is typically used for synthesized values that may not have a
+/// location in the source.
+/// This type alias is provided with a constructor function of the same name
+/// so it is possible to build `MaybeLoc` values with
+/// `MaybeLoc(value, location)`.
+pub type MaybeLoc<T, F, S = Span> = Meta<T, Option<Location<F, S>>>;
+
+/// Build a data with optional `Location` metadata.
+#[allow(non_snake_case)]
+#[doc(hidden)]
+#[inline(always)]
+pub fn MaybeLoc<T, F, S>(t: T, location: Option<Location<F, S>>) -> MaybeLoc<T, F, S> {
+	Meta(t, location)
+}
+
+impl<T, F, S> MaybeLoc<T, F, S> {
+	/// Discards the value and returns its location, if any.
+	#[inline(always)]
+	pub fn into_location(self) -> Option<Location<F, S>> {
+		self.into_metadata()
+	}
+
+	/// Returns a reference to the value's location, if any.
+	#[inline(always)]
+	pub fn location(&self) -> Option<&Location<F, S>> {
+		self.1.as_ref()
+	}
+
+	/// Returns a mutable reference to the value's location, if any.
+	#[inline(always)]
+	pub fn location_mut(&mut self) -> Option<&mut Location<F, S>> {
+		self.1.as_mut()
+	}
+
+	/// Sets the value's location and returns the previous one.
+	#[inline(always)]
+	pub fn set_location(&mut self, location: Option<Location<F, S>>) -> Option<Location<F, S>> {
+		std::mem::replace(&mut self.1, location)
+	}
+
+	/// Returns the value's span, if any.
+	#[inline(always)]
+	pub fn span(&self) -> Option<S>
+	where
+		S: Clone,
+	{
+		self.1.as_ref().map(Location::span)
+	}
+
+	/// Returns a reference to the value's source file, if any.
+	#[inline(always)]
+	pub fn file(&self) -> Option<&F> {
+		self.1.as_ref().map(Location::file)
+	}
+
+	/// Returns the located value, using the given `location` if the value
+	/// has none.
+	///
+	/// ```
+	/// use locspan::{Loc, Location, MaybeLoc, Span};
+	///
+	/// let value = MaybeLoc(1, None);
+	/// let located = value.or_at(Location::new("builtin", Span::default()));
+	/// assert_eq!(*located.file(), "builtin");
+	/// ```
+	#[inline(always)]
+	pub fn or_at(self, location: Location<F, S>) -> Loc<T, F, S> {
+		Meta(self.0, self.1.unwrap_or(location))
+	}
+
+	/// Returns the located value, computing a location with `f` if the value
+	/// has none.
+	#[inline(always)]
+	pub fn or_at_with(self, f: impl FnOnce() -> Location<F, S>) -> Loc<T, F, S> {
+		Meta(self.0, self.1.unwrap_or_else(f))
+	}
+}
+
+impl<T, F, S> From<Loc<T, F, S>> for MaybeLoc<T, F, S> {
+	#[inline(always)]
+	fn from(Meta(t, location): Loc<T, F, S>) -> Self {
+		Meta(t, Some(location))
+	}
+}
+
+impl<T, F, S> MaybeLocated for MaybeLoc<T, F, S> {
+	type File = F;
+	type Span = S;
+
+	fn optional_location(&self) -> Option<&Location<F, S>> {
+		self.1.as_ref()
+	}
+}
+
+/// Attaches a file to every span of a structure.
+///
+/// Parsers commonly produce values with [`Span`] metadata, not knowing which
+/// file they are parsing. This trait converts such a structure into one
+/// whose metadata is a [`Location`] in the given file, using its
+/// [`MapMetadataRecursively`] implementation.
+///
+/// ```
+/// use locspan::{Location, MapMetadataRecursively, Meta, Span, WithFile};
+///
+/// struct Word(String);
+///
+/// impl<M, N> MapMetadataRecursively<M, N> for Word {
+///   type Output = Self;
+///
+///   fn map_metadata_recursively_mut_ref<F>(self, _f: &mut F) -> Self {
+///     self
+///   }
+/// }
+///
+/// let word = Meta(Word("hello".to_string()), Span::new(0, 5));
+/// let located = word.attach_file("main.txt");
+/// assert_eq!(located.1, Location::new("main.txt", Span::new(0, 5)));
+/// ```
+pub trait WithFile<F, S = Span>: MapMetadataRecursively<S, Location<F, S>> {
+	/// Pairs every span with the given file.
+	fn attach_file(self, file: F) -> Self::Output;
+}
+
+impl<T, F: Clone, S> WithFile<F, S> for T
+where
+	T: MapMetadataRecursively<S, Location<F, S>>,
+{
+	#[inline(always)]
+	fn attach_file(self, file: F) -> Self::Output {
+		self.map_metadata_recursively(|span| Location::new(file.clone(), span))
+	}
+}
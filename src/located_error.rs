@@ -0,0 +1,102 @@
+use crate::{Loc, Located, Location, Meta, Span, Spanned};
+use std::fmt;
+
+/// Error with its location, displayed as `file:span: error`.
+///
+/// The `Display` implementation of [`Loc`] only shows the inner error,
+/// so the location is lost once the error is handed to error reporting
+/// libraries that only rely on `Display` (such as `anyhow`).
+/// This wrapper includes the location in the message, while still exposing
+/// the inner error as its [`source`](std::error::Error::source).
+///
+/// ```
+/// use locspan::{Loc, LocatedError, Location, Span};
+///
+/// let error: LocatedError<std::num::ParseIntError, &str> =
+///   Loc("x".parse::<u32>().unwrap_err(), Location::new("main.rs", Span::new(4, 5))).into();
+/// assert_eq!(error.to_string(), "main.rs:4..5: invalid digit found in string");
+/// ```
+///
+/// Like any [`Located`] type, it is also [`MaybeLocated`](crate::MaybeLocated)
+/// and [`MaybeSpanned`](crate::MaybeSpanned):
+///
+/// ```
+/// use locspan::{LocatedError, Location, MaybeLocated, MaybeSpanned, Span, Spanned};
+///
+/// let error: LocatedError<&str, &str> =
+///   LocatedError::new("oops", Location::new("main.rs", Span::new(4, 5)));
+/// assert_eq!(error.span(), Span::new(4, 5));
+/// assert_eq!(error.optional_span(), Some(Span::new(4, 5)));
+/// assert_eq!(error.optional_location(), Some(error.location()));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LocatedError<E, F, S = Span>(pub Loc<E, F, S>);
+
+impl<E, F, S> LocatedError<E, F, S> {
+	/// Creates a new located error.
+	#[inline(always)]
+	pub fn new(error: E, location: Location<F, S>) -> Self {
+		Self(Meta(error, location))
+	}
+
+	/// Returns a reference to the inner error.
+	#[inline(always)]
+	pub fn error(&self) -> &E {
+		self.0.value()
+	}
+
+	/// Returns a reference to the error location.
+	#[inline(always)]
+	pub fn location(&self) -> &Location<F, S> {
+		self.0.metadata()
+	}
+
+	/// Unwraps the located error.
+	#[inline(always)]
+	pub fn into_inner(self) -> Loc<E, F, S> {
+		self.0
+	}
+}
+
+impl<E, F, S> From<Loc<E, F, S>> for LocatedError<E, F, S> {
+	#[inline(always)]
+	fn from(error: Loc<E, F, S>) -> Self {
+		Self(error)
+	}
+}
+
+impl<E, F, S> Located for LocatedError<E, F, S> {
+	type File = F;
+	type Span = S;
+
+	#[inline(always)]
+	fn location(&self) -> &Location<F, S> {
+		self.0.metadata()
+	}
+}
+
+impl<E, F, S: Clone> Spanned for LocatedError<E, F, S> {
+	type Span = S;
+
+	#[inline(always)]
+	fn span(&self) -> Self::Span {
+		self.0 .1.span()
+	}
+}
+
+impl<E: fmt::Display, F: fmt::Display, S: fmt::Display> fmt::Display for LocatedError<E, F, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {}", self.0 .1, self.0 .0)
+	}
+}
+
+impl<E, F, S> std::error::Error for LocatedError<E, F, S>
+where
+	E: 'static + std::error::Error,
+	F: fmt::Debug + fmt::Display,
+	S: fmt::Debug + fmt::Display,
+{
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.0 .0)
+	}
+}
@@ -1,8 +1,13 @@
-use crate::{MaybeSpanned, Meta, Span, Spanned};
+use crate::{MaybeSpanned, Merge, Meta, Span, Spanned};
+use std::cmp::Ordering;
+use std::fmt;
 
 /// Syntax element location.
 ///
-/// Provides a file identifier (of type `F`) and a [`Span`] in this file.
+/// Provides a file identifier (of type `F`) and a span in this file.
+/// The span is a [`Span`] by default, but any span type `S` can be used, such
+/// as a [`CompactSpan`](crate::CompactSpan) or a
+/// [`LineColSpan`](crate::LineColSpan).
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Location<F, S = Span> {
 	/// File id.
@@ -66,6 +71,12 @@ impl<F, S> Location<F, S> {
 		self.span.clone()
 	}
 
+	/// Returns a reference to the span.
+	#[inline(always)]
+	pub fn span_ref(&self) -> &S {
+		&self.span
+	}
+
 	/// Returns a mutable reference to the span.
 	#[inline(always)]
 	pub fn span_mut(&mut self) -> &mut S {
@@ -88,6 +99,30 @@ impl<F, S> Location<F, S> {
 		}
 	}
 
+	/// Maps the span.
+	///
+	/// ```
+	/// use locspan::{LineIndex, Location, Span};
+	///
+	/// let index = LineIndex::new("a\nbc");
+	/// let location = Location::new("file", Span::new(2, 4));
+	/// let location = location.map_span(|span| index.line_col_span(span).unwrap());
+	/// assert_eq!(location.span().start.line, 1);
+	/// ```
+	#[inline(always)]
+	pub fn map_span<P>(self, f: impl FnOnce(S) -> P) -> Location<F, P> {
+		Location {
+			file: self.file,
+			span: f(self.span),
+		}
+	}
+
+	/// Borrows both the file and the span, without cloning them.
+	#[inline(always)]
+	pub fn as_span_ref(&self) -> Location<&F, &S> {
+		Location::new(&self.file, &self.span)
+	}
+
 	/// Copies the span and borrows the file to create a new `Location<&F>`.
 	#[inline(always)]
 	pub fn borrow(&self) -> Location<&F, S>
@@ -116,15 +151,208 @@ impl<F> Location<F> {
 		self
 	}
 
+	/// Shifts both ends of the location span forward by `offset`, and returns
+	/// itself.
+	#[inline(always)]
+	pub fn rebase(self, offset: usize) -> Self {
+		Self::new(self.file, self.span.rebase(offset))
+	}
+
+	/// Converts a location inside an embedded document into a location in the
+	/// outer file, where the embedded document occupies the `outer` location.
+	///
+	/// This is useful for documents embedded in other files, such as
+	/// front-matter, doc comments or string-embedded languages.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	///
+	/// let outer = Location::new("page.md", Span::new(100, 200));
+	/// let inner = Location::new("front-matter", Span::new(4, 9));
+	/// assert_eq!(inner.embedded_in(&outer), Location::new("page.md", Span::new(104, 109)));
+	/// ```
+	#[inline(always)]
+	pub fn embedded_in<G: Clone>(&self, outer: &Location<G>) -> Location<G> {
+		Location::new(outer.file.clone(), self.span.rebase(outer.span.start()))
+	}
+
+	/// Extends the location span up to the end of `other`, and returns itself.
+	///
+	/// The two locations are expected to be in the same file, which is
+	/// checked in debug mode only.
+	#[inline(always)]
+	pub fn until_end_of(self, other: &Self) -> Self
+	where
+		F: PartialEq,
+	{
+		debug_assert!(self.same_file(other), "locations from different files");
+		self.until(other.span.end())
+	}
+}
+
+impl<F, S: Merge> Location<F, S> {
 	/// Append `span` to the location span, and returns itself.
 	#[inline(always)]
-	pub fn with(mut self, span: Span) -> Self {
-		self.span.append(span);
-		self
+	pub fn with(self, span: S) -> Self {
+		Self::new(self.file, self.span.merge(span))
+	}
+
+	/// Computes the union of two locations in the same file.
+	///
+	/// The file of `self` is kept. The two locations are expected to be in
+	/// the same file, which is checked in debug mode only. Use
+	/// [`try_union`](Self::try_union) to always check it.
+	#[inline(always)]
+	pub fn union(self, other: Self) -> Self
+	where
+		F: PartialEq,
+	{
+		debug_assert!(self.same_file(&other), "locations from different files");
+		self.with(other.span)
+	}
+
+	/// Builds the smallest location enclosing the two given locations of the
+	/// same file, including everything in between.
+	///
+	/// This is typically used to compute the location of a parent node from
+	/// the locations of its first and last children.
+	/// The order of the two locations does not matter.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	///
+	/// let first = Location::new("a", Span::new(2, 4));
+	/// let last = Location::new("a", Span::new(8, 9));
+	/// assert_eq!(Location::between(first, last), Location::new("a", Span::new(2, 9)));
+	/// ```
+	#[inline(always)]
+	pub fn between(a: Self, b: Self) -> Self
+	where
+		F: PartialEq,
+	{
+		a.union(b)
+	}
+
+	/// Computes the union of two locations in the same file.
+	///
+	/// Returns an error giving back the two locations if they are not in the
+	/// same file.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	///
+	/// let a = Location::new("a", Span::new(0, 2));
+	/// let b = Location::new("a", Span::new(4, 6));
+	/// assert_eq!(a.try_union(b), Ok(Location::new("a", Span::new(0, 6))));
+	/// assert!(a.try_union(Location::new("b", Span::new(4, 6))).is_err());
+	/// ```
+	#[inline(always)]
+	pub fn try_union(self, other: Self) -> Result<Self, DifferentFiles<F, S>>
+	where
+		F: PartialEq,
+	{
+		if self.same_file(&other) {
+			Ok(self.with(other.span))
+		} else {
+			Err(DifferentFiles(self, other))
+		}
+	}
+}
+
+impl<F: PartialEq, S> Location<F, S> {
+	/// Checks if the two locations are in the same file.
+	#[inline(always)]
+	pub fn same_file<T>(&self, other: &Location<F, T>) -> bool {
+		self.file == other.file
+	}
+}
+
+impl<F, S: Ord> Location<F, S> {
+	/// Compares two locations using the given file order, then by span.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	///
+	/// let order = ["b.rs", "a.rs"];
+	/// let rank = |f: &&str| order.iter().position(|o| o == f);
+	///
+	/// let mut locations = [
+	///   Location::new("a.rs", Span::new(0, 1)),
+	///   Location::new("b.rs", Span::new(2, 3)),
+	///   Location::new("b.rs", Span::new(0, 1)),
+	/// ];
+	/// locations.sort_by(|a, b| a.cmp_with(b, |f, g| rank(f).cmp(&rank(g))));
+	/// assert_eq!(*locations[0].file(), "b.rs");
+	/// assert_eq!(locations[0].span(), Span::new(0, 1));
+	/// assert_eq!(*locations[2].file(), "a.rs");
+	/// ```
+	#[inline(always)]
+	pub fn cmp_with(&self, other: &Self, file_order: impl Fn(&F, &F) -> Ordering) -> Ordering {
+		file_order(&self.file, &other.file).then_with(|| self.span.cmp(&other.span))
+	}
+}
+
+/// Location ordered using a custom file order, then by span.
+///
+/// This adapter can be used to sort locations or to use them as keys of
+/// ordered collections without relying on the `Ord` implementation of the
+/// file type.
+/// All the compared values are expected to use the same file order.
+#[derive(Clone, Copy, Debug)]
+pub struct LocationOrd<O, F, S = Span> {
+	/// Location.
+	pub location: Location<F, S>,
+
+	/// File order.
+	pub file_order: O,
+}
+
+impl<O, F, S> LocationOrd<O, F, S> {
+	/// Wraps the given location with the given file order.
+	#[inline(always)]
+	pub fn new(location: Location<F, S>, file_order: O) -> Self {
+		Self {
+			location,
+			file_order,
+		}
+	}
+}
+
+impl<O: Fn(&F, &F) -> Ordering, F, S: Ord> PartialEq for LocationOrd<O, F, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<O: Fn(&F, &F) -> Ordering, F, S: Ord> Eq for LocationOrd<O, F, S> {}
+
+impl<O: Fn(&F, &F) -> Ordering, F, S: Ord> PartialOrd for LocationOrd<O, F, S> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<O: Fn(&F, &F) -> Ordering, F, S: Ord> Ord for LocationOrd<O, F, S> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.location.cmp_with(&other.location, &self.file_order)
 	}
 }
 
-impl<'a, F: Clone, S: Clone> Location<&'a F, S> {
+/// Error returned when trying to merge locations from different files.
+///
+/// Contains the two locations.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DifferentFiles<F, S = Span>(pub Location<F, S>, pub Location<F, S>);
+
+impl<F, S> fmt::Display for DifferentFiles<F, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "cannot merge locations from different files")
+	}
+}
+
+impl<F: fmt::Debug, S: fmt::Debug> std::error::Error for DifferentFiles<F, S> {}
+
+impl<F: Clone, S: Clone> Location<&F, S> {
 	/// Clones the borrowed file to return a new `Location<F>`.
 	#[inline(always)]
 	pub fn cloned(&self) -> Location<F, S> {
@@ -132,12 +360,58 @@ impl<'a, F: Clone, S: Clone> Location<&'a F, S> {
 	}
 }
 
+impl<F: fmt::Display, S: fmt::Display> fmt::Display for Location<F, S> {
+	/// Formats the location as `file:span`, e.g. `src/main.rs:10..14`.
+	///
+	/// Use [`Location::display_with`] to display a line and column instead.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.file, self.span)
+	}
+}
+
 /// Value with a location.
+///
+/// This trait is independent of [`Spanned`]: a located type is not
+/// automatically spanned, so that both traits can be implemented separately.
+/// The [`located_span`](Self::located_span) method can be used to implement
+/// `Spanned` for a located type:
+///
+/// ```
+/// use locspan::{Located, Location, Span, Spanned};
+///
+/// struct Node(Location<&'static str>);
+///
+/// impl Located for Node {
+///   type File = &'static str;
+///   type Span = Span;
+///
+///   fn location(&self) -> &Location<&'static str> {
+///     &self.0
+///   }
+/// }
+///
+/// impl Spanned for Node {
+///   type Span = Span;
+///
+///   fn span(&self) -> Span {
+///     self.located_span()
+///   }
+/// }
+/// ```
 pub trait Located {
 	type File;
 	type Span;
 
 	fn location(&self) -> &Location<Self::File, Self::Span>;
+
+	/// Returns the span of the location.
+	#[inline(always)]
+	fn located_span(&self) -> Self::Span
+	where
+		Self::Span: Clone,
+	{
+		self.location().span()
+	}
 }
 
 impl<F, S> Located for Location<F, S> {
@@ -149,14 +423,29 @@ impl<F, S> Located for Location<F, S> {
 	}
 }
 
-impl<T: Located> Spanned for T
-where
-	T::Span: Clone,
-{
+impl<T: ?Sized + Located> Located for &T {
+	type File = T::File;
 	type Span = T::Span;
 
+	fn location(&self) -> &Location<Self::File, Self::Span> {
+		T::location(self)
+	}
+}
+
+impl<T: ?Sized + Located> Located for Box<T> {
+	type File = T::File;
+	type Span = T::Span;
+
+	fn location(&self) -> &Location<Self::File, Self::Span> {
+		T::location(self)
+	}
+}
+
+impl<F, S: Clone> Spanned for Location<F, S> {
+	type Span = S;
+
 	fn span(&self) -> Self::Span {
-		self.location().span()
+		self.span()
 	}
 }
 
@@ -166,6 +455,29 @@ pub trait MaybeLocated {
 	type Span;
 
 	fn optional_location(&self) -> Option<&Location<Self::File, Self::Span>>;
+
+	/// Returns the location, or `fallback` if there is none.
+	fn optional_location_or<'a>(
+		&'a self,
+		fallback: &'a Location<Self::File, Self::Span>,
+	) -> &'a Location<Self::File, Self::Span> {
+		self.optional_location().unwrap_or(fallback)
+	}
+
+	/// Returns a copy of the location, or `location` if there is none.
+	fn or_located_at(
+		&self,
+		location: Location<Self::File, Self::Span>,
+	) -> Location<Self::File, Self::Span>
+	where
+		Self::File: Clone,
+		Self::Span: Clone,
+	{
+		match self.optional_location() {
+			Some(l) => l.clone(),
+			None => location,
+		}
+	}
 }
 
 impl<T: MaybeLocated> MaybeSpanned for T
@@ -188,6 +500,15 @@ impl<T: Located> MaybeLocated for T {
 	}
 }
 
+impl<T: Located> MaybeLocated for Option<T> {
+	type File = T::File;
+	type Span = T::Span;
+
+	fn optional_location(&self) -> Option<&Location<Self::File, Self::Span>> {
+		self.as_ref().map(T::location)
+	}
+}
+
 impl<T, F, S> Located for Meta<T, Location<F, S>> {
 	type File = F;
 	type Span = S;
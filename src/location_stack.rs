@@ -0,0 +1,141 @@
+use crate::{Located, Location, Span, Spanned};
+
+/// Location with its expansion chain.
+///
+/// Represents a location expanded from another location, for instance by a
+/// macro expansion, a file inclusion or a template instantiation.
+/// The first location of the chain is where the element actually is, and
+/// each following location is where the previous one was expanded from.
+///
+/// ```
+/// use locspan::{Location, LocationStack, Span};
+///
+/// let stack = LocationStack::new(Location::new("macro.rs", Span::new(4, 8)))
+///   .expanded_from(Location::new("main.rs", Span::new(10, 20)));
+///
+/// let files: Vec<_> = stack.iter().map(|l| *l.file()).collect();
+/// assert_eq!(files, ["macro.rs", "main.rs"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct LocationStack<F, S = Span> {
+	/// Location.
+	location: Location<F, S>,
+
+	/// Location from which this location was expanded.
+	parent: Option<Box<Self>>,
+}
+
+impl<F, S> LocationStack<F, S> {
+	/// Creates a new chain with a single location.
+	#[inline(always)]
+	pub fn new(location: Location<F, S>) -> Self {
+		Self {
+			location,
+			parent: None,
+		}
+	}
+
+	/// Returns the innermost location, where the element actually is.
+	#[inline(always)]
+	pub fn location(&self) -> &Location<F, S> {
+		&self.location
+	}
+
+	/// Returns the chain of the location from which the innermost location
+	/// was expanded, if any.
+	#[inline(always)]
+	pub fn parent(&self) -> Option<&Self> {
+		self.parent.as_deref()
+	}
+
+	/// Returns the outermost location of the chain.
+	#[inline(always)]
+	pub fn root(&self) -> &Location<F, S> {
+		match &self.parent {
+			Some(parent) => parent.root(),
+			None => &self.location,
+		}
+	}
+
+	/// Returns the number of locations in the chain.
+	#[inline(always)]
+	pub fn depth(&self) -> usize {
+		self.iter().count()
+	}
+
+	/// Adds `location` at the end of the chain, as the location from which
+	/// the outermost location was expanded, and returns the chain.
+	pub fn expanded_from(mut self, location: Location<F, S>) -> Self {
+		self.push_parent(location);
+		self
+	}
+
+	/// Adds `location` at the end of the chain, as the location from which
+	/// the outermost location was expanded.
+	pub fn push_parent(&mut self, location: Location<F, S>) {
+		match &mut self.parent {
+			Some(parent) => parent.push_parent(location),
+			None => self.parent = Some(Box::new(Self::new(location))),
+		}
+	}
+
+	/// Returns an iterator over the locations of the chain, from the
+	/// innermost to the outermost.
+	#[inline(always)]
+	pub fn iter(&self) -> LocationStackIter<'_, F, S> {
+		LocationStackIter(Some(self))
+	}
+
+	/// Consumes the chain and returns its innermost location.
+	#[inline(always)]
+	pub fn into_location(self) -> Location<F, S> {
+		self.location
+	}
+}
+
+impl<F, S> From<Location<F, S>> for LocationStack<F, S> {
+	#[inline(always)]
+	fn from(location: Location<F, S>) -> Self {
+		Self::new(location)
+	}
+}
+
+impl<F, S> Located for LocationStack<F, S> {
+	type File = F;
+	type Span = S;
+
+	fn location(&self) -> &Location<F, S> {
+		&self.location
+	}
+}
+
+impl<F, S: Clone> Spanned for LocationStack<F, S> {
+	type Span = S;
+
+	fn span(&self) -> Self::Span {
+		self.location.span()
+	}
+}
+
+impl<'a, F, S> IntoIterator for &'a LocationStack<F, S> {
+	type Item = &'a Location<F, S>;
+	type IntoIter = LocationStackIter<'a, F, S>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Iterator over the locations of a [`LocationStack`].
+pub struct LocationStackIter<'a, F, S = Span>(Option<&'a LocationStack<F, S>>);
+
+impl<'a, F, S> Iterator for LocationStackIter<'a, F, S> {
+	type Item = &'a Location<F, S>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let stack = self.0?;
+		self.0 = stack.parent();
+		Some(&stack.location)
+	}
+}
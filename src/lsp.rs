@@ -0,0 +1,79 @@
+use crate::{ColumnEncoding, LineIndex, Location, Position, Span};
+
+impl LineIndex<'_> {
+	/// Converts a byte offset into an LSP position, with UTF-16 columns.
+	///
+	/// Returns `None` if the offset is past the end of the source text.
+	///
+	/// ```
+	/// use locspan::LineIndex;
+	///
+	/// let index = LineIndex::new("a\n𝔸b");
+	/// let position = index.lsp_position(6).unwrap();
+	/// assert_eq!((position.line, position.character), (1, 2));
+	/// assert_eq!(index.lsp_offset(position), Some(6));
+	/// ```
+	pub fn lsp_position(&self, offset: usize) -> Option<lsp_types::Position> {
+		let position = self.position_with(offset, ColumnEncoding::Utf16)?;
+		Some(lsp_types::Position::new(
+			position.line.try_into().ok()?,
+			position.column.try_into().ok()?,
+		))
+	}
+
+	/// Converts an LSP position, with UTF-16 columns, into a byte offset.
+	///
+	/// Returns `None` if the position is not in the source text.
+	pub fn lsp_offset(&self, position: lsp_types::Position) -> Option<usize> {
+		self.offset_with(
+			Position::new(position.line as usize, position.character as usize),
+			ColumnEncoding::Utf16,
+		)
+	}
+
+	/// Converts a byte span into an LSP range.
+	///
+	/// Returns `None` if the span is past the end of the source text.
+	#[inline(always)]
+	pub fn lsp_range(&self, span: Span) -> Option<lsp_types::Range> {
+		Some(lsp_types::Range::new(
+			self.lsp_position(span.start())?,
+			self.lsp_position(span.end())?,
+		))
+	}
+
+	/// Converts an LSP range into a byte span.
+	///
+	/// Returns `None` if one of the positions is not in the source text.
+	#[inline(always)]
+	pub fn lsp_span(&self, range: lsp_types::Range) -> Option<Span> {
+		Some(Span::new(
+			self.lsp_offset(range.start)?,
+			self.lsp_offset(range.end)?,
+		))
+	}
+}
+
+impl Location<lsp_types::Uri> {
+	/// Converts this location into an LSP location, using the line index of
+	/// its file.
+	///
+	/// Returns `None` if the span is past the end of the source text.
+	#[inline(always)]
+	pub fn to_lsp(&self, index: &LineIndex) -> Option<lsp_types::Location> {
+		Some(lsp_types::Location::new(
+			self.file().clone(),
+			index.lsp_range(self.span())?,
+		))
+	}
+
+	/// Converts an LSP location into a location, using the line index of its
+	/// file.
+	///
+	/// Returns `None` if the range is not in the source text.
+	#[inline(always)]
+	pub fn from_lsp(location: lsp_types::Location, index: &LineIndex) -> Option<Self> {
+		let span = index.lsp_span(location.range)?;
+		Some(Self::new(location.uri, span))
+	}
+}
@@ -0,0 +1,102 @@
+use crate::{Location, Span};
+
+/// Iterator over the locations of matches found in a source file.
+///
+/// Wraps any iterator of `(start, end)` byte ranges produced by a matcher
+/// (such as a regular expression engine driven by the caller) and yields
+/// the corresponding [`Location`] in the given file.
+pub struct Matches<F, I> {
+	/// File in which the matches are found.
+	file: F,
+
+	/// Matches.
+	matches: I,
+}
+
+impl<F, I> Matches<F, I> {
+	/// Creates a new iterator over the locations of the given `matches`
+	/// found in `file`.
+	#[inline(always)]
+	pub fn new(file: F, matches: impl IntoIterator<IntoIter = I>) -> Self {
+		Self {
+			file,
+			matches: matches.into_iter(),
+		}
+	}
+
+	/// Returns a reference to the file in which the matches are found.
+	#[inline(always)]
+	pub fn file(&self) -> &F {
+		&self.file
+	}
+}
+
+impl<F: Clone, I: Iterator<Item = (usize, usize)>> Iterator for Matches<F, I> {
+	type Item = Location<F>;
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.matches.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.matches
+			.next()
+			.map(|(start, end)| Location::new(self.file.clone(), Span::new(start, end)))
+	}
+}
+
+/// Iterator over the locations of matches found in multiple source files.
+///
+/// The `matcher` function is called on each source in turn, and must return
+/// the `(start, end)` byte ranges of the matches found in this source.
+pub struct SourcesMatches<S, P, F, I> {
+	/// Remaining sources, paired with their file identifier.
+	sources: S,
+
+	/// Matcher function.
+	matcher: P,
+
+	/// Matches in the current source.
+	current: Option<Matches<F, I>>,
+}
+
+impl<S, P, F, T, M> SourcesMatches<S, P, F, M::IntoIter>
+where
+	S: Iterator<Item = (F, T)>,
+	P: FnMut(T) -> M,
+	M: IntoIterator<Item = (usize, usize)>,
+{
+	/// Creates a new iterator over the locations of the matches found by
+	/// `matcher` in the given `sources`.
+	#[inline(always)]
+	pub fn new(sources: impl IntoIterator<IntoIter = S>, matcher: P) -> Self {
+		Self {
+			sources: sources.into_iter(),
+			matcher,
+			current: None,
+		}
+	}
+}
+
+impl<S, P, F, T, M> Iterator for SourcesMatches<S, P, F, M::IntoIter>
+where
+	S: Iterator<Item = (F, T)>,
+	P: FnMut(T) -> M,
+	M: IntoIterator<Item = (usize, usize)>,
+	F: Clone,
+{
+	type Item = Location<F>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(current) = &mut self.current {
+				if let Some(location) = current.next() {
+					break Some(location);
+				}
+			}
+
+			let (file, source) = self.sources.next()?;
+			self.current = Some(Matches::new(file, (self.matcher)(source)))
+		}
+	}
+}
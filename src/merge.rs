@@ -0,0 +1,47 @@
+use crate::{Location, Span};
+
+/// Metadata that can be merged.
+///
+/// Used to compute the metadata of a value from the metadata of its parts.
+/// For spans, merging computes the union of the two spans.
+pub trait Merge {
+	/// Merges `self` with `other`.
+	fn merge(self, other: Self) -> Self;
+}
+
+impl Merge for () {
+	#[inline(always)]
+	fn merge(self, _other: Self) -> Self {}
+}
+
+impl Merge for Span {
+	#[inline(always)]
+	fn merge(self, other: Self) -> Self {
+		self.union(other)
+	}
+}
+
+impl<F, S: Merge> Merge for Location<F, S> {
+	/// Merges the spans of the two locations.
+	///
+	/// The file of `self` is kept, the file of `other` is discarded.
+	#[inline(always)]
+	fn merge(self, other: Self) -> Self {
+		let (file, span) = self.into_parts();
+		Location::new(file, span.merge(other.into_span()))
+	}
+}
+
+impl<M: Merge> Merge for Option<M> {
+	/// Merges the two optional values.
+	///
+	/// If only one of the two is `Some`, it is returned.
+	#[inline(always)]
+	fn merge(self, other: Self) -> Self {
+		match (self, other) {
+			(Some(a), Some(b)) => Some(a.merge(b)),
+			(Some(a), None) => Some(a),
+			(None, b) => b,
+		}
+	}
+}
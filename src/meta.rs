@@ -1,12 +1,35 @@
-use std::borrow::{Borrow, BorrowMut};
+use crate::{Loc, Located, Location, Merge, Spanned};
+use std::borrow::{Borrow, BorrowMut, Cow};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
-use std::ops::{Deref, DerefMut};
+use std::future::Future;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[cfg(feature = "hashbrown")]
+use hashbrown::{HashMap as HashbrownMap, HashSet as HashbrownSet};
+
+#[cfg(feature = "indexmap")]
+use indexmap::{IndexMap, IndexSet};
 
 /// Data and its metadata.
 ///
 /// This is a simple wrapper around data that also embeds data of type `M`.
 ///
 /// It is a tuple struct so it can be easily deconstructed using pattern matching.
+///
+/// Arithmetic and bitwise operators are forwarded to the values. The metadata
+/// of binary operations is the [merge](Merge) of the operands metadata:
+///
+/// ```
+/// use locspan::{Meta, Span};
+///
+/// let sum = Meta(1, Span::new(0, 1)) + Meta(2, Span::new(4, 5));
+/// assert_eq!(sum, Meta(3, Span::new(0, 5)));
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Meta<T, M = ()>(pub T, pub M);
 
@@ -29,6 +52,12 @@ impl<T, M> Meta<T, M> {
 		self.1
 	}
 
+	/// Splits the value and its metadata.
+	#[inline(always)]
+	pub fn into_parts(self) -> (T, M) {
+		(self.0, self.1)
+	}
+
 	/// Returns a reference to the wrapped value.
 	#[inline(always)]
 	pub fn value(&self) -> &T {
@@ -53,12 +82,122 @@ impl<T, M> Meta<T, M> {
 		&mut self.1
 	}
 
+	/// Returns mutable references to both the value and its metadata.
+	#[inline(always)]
+	pub fn parts_mut(&mut self) -> (&mut T, &mut M) {
+		(&mut self.0, &mut self.1)
+	}
+
+	/// Updates the value in place.
+	#[inline(always)]
+	pub fn update_value(&mut self, f: impl FnOnce(&mut T)) {
+		f(&mut self.0)
+	}
+
+	/// Updates the metadata in place.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let mut call = Meta("f(x", Span::new(0, 3));
+	/// call.update_metadata(|span| span.append(Span::new(3, 4)));
+	/// assert_eq!(call, Meta("f(x", Span::new(0, 4)));
+	/// ```
+	#[inline(always)]
+	pub fn update_metadata(&mut self, f: impl FnOnce(&mut M)) {
+		f(&mut self.1)
+	}
+
+	/// Replaces the value, returning the previous one.
+	#[inline(always)]
+	pub fn replace_value(&mut self, value: T) -> T {
+		std::mem::replace(&mut self.0, value)
+	}
+
+	/// Replaces the metadata, returning the previous one.
+	#[inline(always)]
+	pub fn replace_metadata(&mut self, metadata: M) -> M {
+		std::mem::replace(&mut self.1, metadata)
+	}
+
+	/// Takes the value, leaving `T::default()` in its place.
+	///
+	/// The metadata is left untouched.
+	#[inline(always)]
+	pub fn take_value(&mut self) -> T
+	where
+		T: Default,
+	{
+		std::mem::take(&mut self.0)
+	}
+
 	/// Maps the inner value.
 	#[inline(always)]
 	pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Meta<U, M> {
 		Meta(f(self.0), self.1)
 	}
 
+	/// Maps the inner value using an asynchronous function.
+	#[inline(always)]
+	pub async fn map_async<U, Fut>(self, f: impl FnOnce(T) -> Fut) -> Meta<U, M>
+	where
+		Fut: Future<Output = U>,
+	{
+		Meta(f(self.0).await, self.1)
+	}
+
+	/// Maps the metadata using an asynchronous function.
+	#[inline(always)]
+	pub async fn map_metadata_async<N, Fut>(self, f: impl FnOnce(M) -> Fut) -> Meta<T, N>
+	where
+		Fut: Future<Output = N>,
+	{
+		Meta(self.0, f(self.1).await)
+	}
+
+	/// Combines the value with the value of `other` using `combine_values`,
+	/// and the metadata of both using `merge_metadata`.
+	///
+	/// Typically used to combine two located errors into one.
+	#[inline(always)]
+	pub fn merge_with<U, V, N>(
+		self,
+		other: Meta<U, N>,
+		combine_values: impl FnOnce(T, U) -> V,
+		merge_metadata: impl FnOnce(M, N) -> M,
+	) -> Meta<V, M> {
+		Meta(
+			combine_values(self.0, other.0),
+			merge_metadata(self.1, other.1),
+		)
+	}
+
+	/// Pairs the value with the value of `other`, combining both metadata
+	/// with the `combine` function.
+	#[inline(always)]
+	pub fn zip<U>(self, other: Meta<U, M>, combine: impl FnOnce(M, M) -> M) -> Meta<(T, U), M> {
+		Meta((self.0, other.0), combine(self.1, other.1))
+	}
+
+	/// Pairs the value with the value of `other`, merging both metadata.
+	///
+	/// For spans and locations, the resulting metadata covers both operands.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let lhs = Meta(1, Span::new(0, 1));
+	/// let rhs = Meta(2, Span::new(4, 5));
+	/// assert_eq!(lhs.zip_merged(rhs), Meta((1, 2), Span::new(0, 5)));
+	/// ```
+	#[inline(always)]
+	pub fn zip_merged<U>(self, other: Meta<U, M>) -> Meta<(T, U), M>
+	where
+		M: Merge,
+	{
+		self.zip(other, M::merge)
+	}
+
 	/// Converts the inner value.
 	#[inline(always)]
 	pub fn cast<U>(self) -> Meta<U, M>
@@ -194,6 +333,73 @@ impl<T, M> Meta<T, M> {
 		Meta(&self.0, &self.1)
 	}
 
+	/// Borrows the value and its metadata.
+	///
+	/// Same as [`Meta::borrow`], named after [`Iterator::by_ref`] to pair
+	/// with [`Meta::by_mut`].
+	#[inline(always)]
+	pub fn by_ref(&self) -> Meta<&T, &M> {
+		self.borrow()
+	}
+
+	/// Mutably borrows the value and its metadata.
+	///
+	/// This allows mutating the value while reading (or updating) the
+	/// metadata at the same time.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let mut word = Meta(String::from("foo"), Span::new(0, 3));
+	/// let Meta(value, span) = word.by_mut();
+	/// value.push_str("bar");
+	/// span.set_end(span.end() + 3);
+	/// assert_eq!(word, Meta(String::from("foobar"), Span::new(0, 6)));
+	/// ```
+	#[inline(always)]
+	pub fn by_mut(&mut self) -> Meta<&mut T, &mut M> {
+		Meta(&mut self.0, &mut self.1)
+	}
+
+	/// Moves the metadata into a `Box`.
+	///
+	/// This keeps values small when the metadata is large.
+	/// See also the [`BoxedLoc`](crate::BoxedLoc) type alias.
+	#[inline(always)]
+	pub fn box_metadata(self) -> Meta<T, Box<M>> {
+		Meta(self.0, Box::new(self.1))
+	}
+
+	/// Borrows a part of the value (typically a field) along with the
+	/// metadata.
+	///
+	/// See also the [`meta_project!`](crate::meta_project) macro.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// struct Call {
+	///   function: String,
+	///   args: Vec<u32>,
+	/// }
+	///
+	/// let call = Meta(Call { function: "f".to_string(), args: vec![1, 2] }, Span::new(0, 7));
+	/// let Meta(args, span) = call.field(|c| &c.args);
+	/// assert_eq!(args.len(), 2);
+	/// assert_eq!(*span, Span::new(0, 7));
+	/// ```
+	#[inline(always)]
+	pub fn field<U: ?Sized>(&self, f: impl FnOnce(&T) -> &U) -> Meta<&U, &M> {
+		Meta(f(&self.0), &self.1)
+	}
+
+	/// Mutably borrows a part of the value (typically a field) along with
+	/// the metadata.
+	#[inline(always)]
+	pub fn field_mut<U: ?Sized>(&mut self, f: impl FnOnce(&mut T) -> &mut U) -> Meta<&mut U, &M> {
+		Meta(f(&mut self.0), &self.1)
+	}
+
 	/// Borrows the value and clones the metadata.
 	#[inline(always)]
 	pub fn borrow_value(&self) -> Meta<&T, M>
@@ -211,6 +417,62 @@ impl<T, M> Meta<T, M> {
 	{
 		Meta(self.0.clone(), &self.1)
 	}
+
+	/// Borrows the dereferenced value and the metadata.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let boxed: Meta<Box<u32>, Span> = Meta(Box::new(42), Span::new(0, 2));
+	/// let Meta(value, span) = boxed.as_deref();
+	/// assert_eq!(*value, 42);
+	/// assert_eq!(*span, Span::new(0, 2));
+	/// ```
+	#[inline(always)]
+	pub fn as_deref(&self) -> Meta<&T::Target, &M>
+	where
+		T: Deref,
+	{
+		Meta(self.0.deref(), &self.1)
+	}
+
+	/// Mutably borrows the dereferenced value and the metadata.
+	#[inline(always)]
+	pub fn as_deref_mut(&mut self) -> Meta<&mut T::Target, &mut M>
+	where
+		T: DerefMut,
+	{
+		Meta(self.0.deref_mut(), &mut self.1)
+	}
+}
+
+impl<T, M> Meta<T, M> {
+	/// Projects a pinned reference to the value and a shared reference to
+	/// its metadata.
+	///
+	/// The value is structurally pinned: a pinned `Meta<T, M>` never moves
+	/// its value. The metadata is not.
+	#[inline(always)]
+	pub fn project(self: Pin<&mut Self>) -> Meta<Pin<&mut T>, &M> {
+		// SAFETY: `Meta` does not implement `Drop`, is not `repr(packed)` and
+		// is only `Unpin` when `T` (and `M`) are, so the value is never moved
+		// out of a pinned `Meta`.
+		unsafe {
+			let this = self.get_unchecked_mut();
+			Meta(Pin::new_unchecked(&mut this.0), &this.1)
+		}
+	}
+
+	/// Projects a pinned shared reference to the value and a shared
+	/// reference to its metadata.
+	#[inline(always)]
+	pub fn project_ref(self: Pin<&Self>) -> Meta<Pin<&T>, &M> {
+		// SAFETY: see `project`.
+		unsafe {
+			let this = self.get_ref();
+			Meta(Pin::new_unchecked(&this.0), &this.1)
+		}
+	}
 }
 
 impl<T> Meta<T> {
@@ -220,6 +482,26 @@ impl<T> Meta<T> {
 	}
 }
 
+impl<T, M: Default> Meta<T, M> {
+	/// Creates a new value attached to the default metadata.
+	///
+	/// Same as the `From<T>` implementation, with a more explicit name.
+	#[inline(always)]
+	pub fn with_default_metadata(t: T) -> Self {
+		Self(t, M::default())
+	}
+}
+
+impl<T: Default, M: Default> Default for Meta<T, M> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self(T::default(), M::default())
+	}
+}
+
+/// Attaches the default metadata to the value.
+///
+/// Prefer [`Meta::with_default_metadata`], which is more explicit.
 impl<T, M> From<T> for Meta<T, M>
 where
 	M: Default,
@@ -229,13 +511,27 @@ where
 	}
 }
 
-impl<'t, 'm, T: Clone, M: Clone> Meta<&'t T, &'m M> {
+impl<T, M> From<(T, M)> for Meta<T, M> {
+	#[inline(always)]
+	fn from((t, metadata): (T, M)) -> Self {
+		Self(t, metadata)
+	}
+}
+
+impl<T, M> From<Meta<T, M>> for (T, M) {
+	#[inline(always)]
+	fn from(Meta(t, metadata): Meta<T, M>) -> Self {
+		(t, metadata)
+	}
+}
+
+impl<T: Clone, M: Clone> Meta<&T, &M> {
 	pub fn cloned(&self) -> Meta<T, M> {
 		Meta(self.0.clone(), self.1.clone())
 	}
 }
 
-impl<'t, T: Clone, M> Meta<&'t T, M> {
+impl<T: Clone, M> Meta<&T, M> {
 	/// Clones the borrowed value and the file to return a new `Meta<T, F>`.
 	#[inline(always)]
 	pub fn cloned_value(&self) -> Meta<T, M>
@@ -252,7 +548,7 @@ impl<'t, T: Clone, M> Meta<&'t T, M> {
 	}
 }
 
-impl<'m, T, M: Clone> Meta<T, &'m M> {
+impl<T, M: Clone> Meta<T, &M> {
 	#[inline(always)]
 	pub fn cloned_metadata(&self) -> Meta<T, M>
 	where
@@ -267,6 +563,91 @@ impl<'m, T, M: Clone> Meta<T, &'m M> {
 	}
 }
 
+impl<'a, T, M: ?Sized + ToOwned> Meta<T, Cow<'a, M>> {
+	/// Creates a new value attached to borrowed metadata.
+	#[inline(always)]
+	pub fn borrowed_metadata(t: T, metadata: &'a M) -> Self {
+		Self(t, Cow::Borrowed(metadata))
+	}
+
+	/// Creates a new value attached to owned metadata.
+	#[inline(always)]
+	pub fn owned_metadata(t: T, metadata: M::Owned) -> Self {
+		Self(t, Cow::Owned(metadata))
+	}
+
+	/// Converts the metadata into owned metadata, cloning it if it is
+	/// borrowed.
+	///
+	/// ```
+	/// use std::borrow::Cow;
+	/// use locspan::{Location, Meta, Span};
+	///
+	/// let location = Location::new("main.rs".to_string(), Span::new(0, 3));
+	/// let token = Meta::borrowed_metadata("foo", &location);
+	/// let owned: Meta<&str, Location<String>> = token.into_owned_metadata();
+	/// assert_eq!(owned, Meta("foo", location));
+	/// ```
+	#[inline(always)]
+	pub fn into_owned_metadata(self) -> Meta<T, M::Owned> {
+		Meta(self.0, self.1.into_owned())
+	}
+}
+
+impl<'a, T: ?Sized + ToOwned, M> Meta<Cow<'a, T>, M> {
+	/// Creates a new borrowed value attached to its metadata.
+	#[inline(always)]
+	pub fn borrowed(t: &'a T, metadata: M) -> Self {
+		Self(Cow::Borrowed(t), metadata)
+	}
+
+	/// Creates a new owned value attached to its metadata.
+	#[inline(always)]
+	pub fn owned(t: T::Owned, metadata: M) -> Self {
+		Self(Cow::Owned(t), metadata)
+	}
+
+	/// Converts the value into an owned value, cloning it if it is borrowed.
+	#[inline(always)]
+	pub fn into_owned(self) -> Meta<T::Owned, M> {
+		Meta(self.0.into_owned(), self.1)
+	}
+
+	/// Returns a mutable reference to the owned value, cloning it first if
+	/// it is borrowed.
+	///
+	/// The metadata is left untouched.
+	///
+	/// ```
+	/// use std::borrow::Cow;
+	/// use locspan::{Meta, Span};
+	///
+	/// let source = String::from("foo");
+	/// let mut ident = Meta::borrowed(source.as_str(), Span::new(0, 3));
+	/// ident.to_mut().push_str("_renamed");
+	/// assert!(matches!(ident.value(), Cow::Owned(_)));
+	/// assert_eq!(ident.into_owned(), Meta("foo_renamed".to_string(), Span::new(0, 3)));
+	/// ```
+	#[inline(always)]
+	pub fn to_mut(&mut self) -> &mut T::Owned {
+		self.0.to_mut()
+	}
+}
+
+impl<'a, T, M: Clone> From<Meta<T, M>> for Meta<T, Cow<'a, M>> {
+	#[inline(always)]
+	fn from(Meta(t, metadata): Meta<T, M>) -> Self {
+		Self(t, Cow::Owned(metadata))
+	}
+}
+
+impl<'a, T, M: Clone> From<Meta<T, &'a M>> for Meta<T, Cow<'a, M>> {
+	#[inline(always)]
+	fn from(Meta(t, metadata): Meta<T, &'a M>) -> Self {
+		Self(t, Cow::Borrowed(metadata))
+	}
+}
+
 impl<T, M> Meta<Option<T>, M> {
 	/// Unwraps the inner `Option`.
 	#[inline(always)]
@@ -283,6 +664,88 @@ impl<T, M> Meta<Option<T>, M> {
 	}
 }
 
+impl<T, M> Meta<T, Box<M>> {
+	/// Moves the metadata out of its `Box`.
+	#[inline(always)]
+	pub fn unbox_metadata(self) -> Meta<T, M> {
+		Meta(self.0, *self.1)
+	}
+}
+
+impl<T, E, M> Meta<Result<T, E>, M> {
+	/// Transposes a located result into a result of located values.
+	///
+	/// The metadata is attached to either the success value or the error.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let result: Meta<Result<u32, &str>, Span> = Meta(Err("invalid"), Span::new(2, 4));
+	/// assert_eq!(result.transpose(), Err(Meta("invalid", Span::new(2, 4))));
+	/// ```
+	#[inline(always)]
+	pub fn transpose(self) -> Result<Meta<T, M>, Meta<E, M>> {
+		match self.0 {
+			Ok(t) => Ok(Meta(t, self.1)),
+			Err(e) => Err(Meta(e, self.1)),
+		}
+	}
+
+	/// Transposes a located result into a result of located values,
+	/// discarding the metadata in case of error.
+	#[inline(always)]
+	pub fn transpose_ok(self) -> Result<Meta<T, M>, E> {
+		match self.0 {
+			Ok(t) => Ok(Meta(t, self.1)),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+impl<T, M> Meta<Meta<T, M>, M> {
+	/// Collapses two layers of metadata into one, combining the outer and
+	/// inner metadata (in this order) with the `merge` function.
+	#[inline(always)]
+	pub fn flatten(self, merge: impl FnOnce(M, M) -> M) -> Meta<T, M> {
+		let Meta(Meta(t, inner), outer) = self;
+		Meta(t, merge(outer, inner))
+	}
+
+	/// Collapses two layers of metadata into one by merging them.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let nested = Meta(Meta('a', Span::new(1, 2)), Span::new(0, 3));
+	/// assert_eq!(nested.flatten_merged(), Meta('a', Span::new(0, 3)));
+	/// ```
+	#[inline(always)]
+	pub fn flatten_merged(self) -> Meta<T, M>
+	where
+		M: Merge,
+	{
+		self.flatten(M::merge)
+	}
+}
+
+impl<A, B, M: Clone> Meta<(A, B), M> {
+	/// Splits the pair into two values sharing the same metadata.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let entry = Meta(("key", 42), Span::new(0, 7));
+	/// let (key, value) = entry.unzip();
+	/// assert_eq!(key, Meta("key", Span::new(0, 7)));
+	/// assert_eq!(value, Meta(42, Span::new(0, 7)));
+	/// ```
+	#[inline(always)]
+	pub fn unzip(self) -> (Meta<A, M>, Meta<B, M>) {
+		let Meta((a, b), m) = self;
+		(Meta(a, m.clone()), Meta(b, m))
+	}
+}
+
 impl<T, M> Deref for Meta<T, M> {
 	type Target = T;
 
@@ -299,6 +762,22 @@ impl<T, M> DerefMut for Meta<T, M> {
 	}
 }
 
+impl<T: Index<I>, I, M> Index<I> for Meta<T, M> {
+	type Output = T::Output;
+
+	#[inline(always)]
+	fn index(&self, index: I) -> &T::Output {
+		self.0.index(index)
+	}
+}
+
+impl<T: IndexMut<I>, I, M> IndexMut<I> for Meta<T, M> {
+	#[inline(always)]
+	fn index_mut(&mut self, index: I) -> &mut T::Output {
+		self.0.index_mut(index)
+	}
+}
+
 impl<T, M> AsRef<T> for Meta<T, M> {
 	#[inline(always)]
 	fn as_ref(&self) -> &T {
@@ -333,6 +812,70 @@ impl<T: fmt::Display, M> fmt::Display for Meta<T, M> {
 	}
 }
 
+impl<T: fmt::Display, M: fmt::Display> Meta<T, M> {
+	/// Returns a value displaying the value followed by its metadata, in the
+	/// form `value (at metadata)`.
+	///
+	/// ```
+	/// use locspan::{Location, Meta, Span};
+	///
+	/// let token = Meta("foo", Location::new("main.rs", Span::new(2, 5)));
+	/// assert_eq!(token.display_located().to_string(), "foo (at main.rs:2..5)");
+	/// ```
+	#[inline(always)]
+	pub fn display_located(&self) -> DisplayLocated<'_, T, M> {
+		DisplayLocated(self)
+	}
+}
+
+/// Displays a value followed by its metadata.
+///
+/// Created by the [`Meta::display_located`] method.
+pub struct DisplayLocated<'a, T, M>(&'a Meta<T, M>);
+
+impl<T: fmt::Display, M: fmt::Display> fmt::Display for DisplayLocated<'_, T, M> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (at {})", self.0 .0, self.0 .1)
+	}
+}
+
+impl<T, M> Meta<T, M> {
+	/// Returns a value displaying the value and its metadata using the given
+	/// formatting function.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let token = Meta(42, Span::new(2, 4));
+	/// let display = token.display_with(|value, span, f| write!(f, "{span}: {value}"));
+	/// assert_eq!(display.to_string(), "2..4: 42");
+	/// ```
+	#[inline(always)]
+	pub fn display_with<F>(&self, f: F) -> DisplayWith<'_, T, M, F>
+	where
+		F: Fn(&T, &M, &mut fmt::Formatter) -> fmt::Result,
+	{
+		DisplayWith { meta: self, f }
+	}
+}
+
+/// Displays a value and its metadata using a formatting function.
+///
+/// Created by the [`Meta::display_with`] method.
+pub struct DisplayWith<'a, T, M, F> {
+	meta: &'a Meta<T, M>,
+	f: F,
+}
+
+impl<T, M, F> fmt::Display for DisplayWith<'_, T, M, F>
+where
+	F: Fn(&T, &M, &mut fmt::Formatter) -> fmt::Result,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(self.f)(&self.meta.0, &self.meta.1, f)
+	}
+}
+
 #[cfg(feature = "contextual")]
 impl<N, T: contextual::DisplayWithContext<N>, M> contextual::DisplayWithContext<N> for Meta<T, M> {
 	fn fmt_with(&self, context: &N, f: &mut fmt::Formatter) -> fmt::Result {
@@ -346,6 +889,36 @@ impl<T: 'static + std::error::Error, M: fmt::Debug> std::error::Error for Meta<T
 	}
 }
 
+/// Borrows a field of a `Meta` value along with its metadata.
+///
+/// `meta_project!(meta, field)` is equivalent to
+/// `meta.field(|value| &value.field)`, and `meta_project!(mut meta, field)`
+/// to `meta.field_mut(|value| &mut value.field)`.
+///
+/// ```
+/// use locspan::{meta_project, Meta, Span};
+///
+/// struct Binding {
+///   name: String,
+///   value: u32,
+/// }
+///
+/// let mut binding = Meta(Binding { name: "x".to_string(), value: 1 }, Span::new(0, 5));
+/// assert_eq!(meta_project!(binding, name), Meta(&"x".to_string(), &Span::new(0, 5)));
+///
+/// *meta_project!(mut binding, value).0 += 1;
+/// assert_eq!(binding.value, 2);
+/// ```
+#[macro_export]
+macro_rules! meta_project {
+	(mut $meta:expr, $field:tt) => {
+		$crate::Meta::field_mut(&mut $meta, |value| &mut value.$field)
+	};
+	($meta:expr, $field:tt) => {
+		$crate::Meta::field(&$meta, |value| &value.$field)
+	};
+}
+
 /// Provides the `at` function to locate any value.
 ///
 /// This trait is implemented for all types.
@@ -355,6 +928,47 @@ pub trait At: Sized {
 	/// Equivalent to `Meta(self, metadata)`.
 	/// Usually called with a [`Location`](crate::Location) to locate a value in a source file.
 	fn at<M>(self, metadata: M) -> Meta<Self, M>;
+
+	/// Wraps `self` inside a `Meta` using the span of `other` as metadata.
+	#[inline(always)]
+	fn at_span_of<S: Spanned>(self, other: &S) -> Meta<Self, S::Span> {
+		self.at(other.span())
+	}
+
+	/// Wraps `self` inside a `Loc` using (a copy of) the location of `other`.
+	#[inline(always)]
+	fn at_location_of<L: Located>(self, other: &L) -> Loc<Self, L::File, L::Span>
+	where
+		L::File: Clone,
+		L::Span: Clone,
+	{
+		self.at(other.location().clone())
+	}
+
+	/// Wraps `self` inside a `Loc` located between `a` and `b`, using the
+	/// union of their locations.
+	///
+	/// Typically used to locate a parent node at its children.
+	///
+	/// ```
+	/// use locspan::{At, Loc, Location, Span};
+	///
+	/// let lhs = Loc(1, Location::new("a", Span::new(0, 1)));
+	/// let rhs = Loc(2, Location::new("a", Span::new(4, 5)));
+	/// let sum = (*lhs + *rhs).between(&lhs, &rhs);
+	/// assert_eq!(sum, Loc(3, Location::new("a", Span::new(0, 5))));
+	/// ```
+	#[inline(always)]
+	fn between<L: Located>(self, a: &L, b: &L) -> Loc<Self, L::File, L::Span>
+	where
+		L::File: Clone + PartialEq,
+		L::Span: Clone + Merge,
+	{
+		self.at(Location::between(
+			a.location().clone(),
+			b.location().clone(),
+		))
+	}
 }
 
 impl<T> At for T {
@@ -402,6 +1016,217 @@ where
 	}
 }
 
+macro_rules! leaf {
+	($($id:ty),*) => {
+		$(
+			impl<M, N> MapMetadataRecursively<M, N> for $id {
+				type Output = Self;
+
+				#[inline(always)]
+				fn map_metadata_recursively_mut_ref<F>(self, _f: &mut F) -> Self
+				where
+					F: FnMut(M) -> N,
+				{
+					self
+				}
+			}
+
+			impl<M, N, E> TryMapMetadataRecursively<M, N, E> for $id {
+				type Output = Self;
+
+				#[inline(always)]
+				fn try_map_metadata_recursively_mut_ref<F>(self, _f: &mut F) -> Result<Self, E>
+				where
+					F: FnMut(M) -> Result<N, E>,
+				{
+					Ok(self)
+				}
+			}
+		)*
+	};
+}
+
+leaf! {
+	(),
+	bool,
+	u8,
+	u16,
+	u32,
+	u64,
+	u128,
+	usize,
+	i8,
+	i16,
+	i32,
+	i64,
+	i128,
+	isize,
+	f32,
+	f64,
+	char,
+	String
+}
+
+impl<T, M, N> MapMetadataRecursively<M, N> for Box<T>
+where
+	T: MapMetadataRecursively<M, N>,
+{
+	type Output = Box<T::Output>;
+
+	#[inline(always)]
+	fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+	where
+		F: FnMut(M) -> N,
+	{
+		Box::new((*self).map_metadata_recursively_mut_ref(f))
+	}
+}
+
+impl<T, M, N> MapMetadataRecursively<M, N> for Rc<T>
+where
+	T: Clone + MapMetadataRecursively<M, N>,
+{
+	type Output = Rc<T::Output>;
+
+	/// Maps the metadata of the shared value.
+	///
+	/// The value is cloned if it is shared.
+	#[inline(always)]
+	fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+	where
+		F: FnMut(M) -> N,
+	{
+		Rc::new(Rc::unwrap_or_clone(self).map_metadata_recursively_mut_ref(f))
+	}
+}
+
+impl<T, M, N> MapMetadataRecursively<M, N> for Arc<T>
+where
+	T: Clone + MapMetadataRecursively<M, N>,
+{
+	type Output = Arc<T::Output>;
+
+	/// Maps the metadata of the shared value.
+	///
+	/// The value is cloned if it is shared.
+	#[inline(always)]
+	fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+	where
+		F: FnMut(M) -> N,
+	{
+		Arc::new(Arc::unwrap_or_clone(self).map_metadata_recursively_mut_ref(f))
+	}
+}
+
+impl<T, M, N> MapMetadataRecursively<M, N> for Option<T>
+where
+	T: MapMetadataRecursively<M, N>,
+{
+	type Output = Option<T::Output>;
+
+	#[inline(always)]
+	fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+	where
+		F: FnMut(M) -> N,
+	{
+		self.map(|t| t.map_metadata_recursively_mut_ref(f))
+	}
+}
+
+macro_rules! map_metadata_recursively_tuple {
+	($($t:ident),*) => {
+		impl<$($t,)* M, N> MapMetadataRecursively<M, N> for ($($t,)*)
+		where
+			$($t: MapMetadataRecursively<M, N>,)*
+		{
+			type Output = ($($t::Output,)*);
+
+			#[allow(non_snake_case)]
+			#[inline(always)]
+			fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+			where
+				F: FnMut(M) -> N,
+			{
+				let ($($t,)*) = self;
+				($($t.map_metadata_recursively_mut_ref(f),)*)
+			}
+		}
+	};
+}
+
+map_metadata_recursively_tuple!(A);
+map_metadata_recursively_tuple!(A, B);
+map_metadata_recursively_tuple!(A, B, C);
+map_metadata_recursively_tuple!(A, B, C, D);
+map_metadata_recursively_tuple!(A, B, C, D, E);
+map_metadata_recursively_tuple!(A, B, C, D, E, G);
+
+macro_rules! map_metadata_recursively_collection {
+	($($(#[$cfg:meta])? $ty:ident < T $(: $($bound:ident)+)? >),*) => {
+		$(
+			$(#[$cfg])?
+			impl<T, M, N> MapMetadataRecursively<M, N> for $ty<T>
+			where
+				T: MapMetadataRecursively<M, N>,
+				$(T::Output: $($bound +)*)?
+			{
+				type Output = $ty<T::Output>;
+
+				fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+				where
+					F: FnMut(M) -> N,
+				{
+					self.into_iter()
+						.map(|t| t.map_metadata_recursively_mut_ref(f))
+						.collect()
+				}
+			}
+		)*
+	};
+}
+
+map_metadata_recursively_collection! {
+	Vec<T>,
+	HashSet<T: Eq Hash>,
+	BTreeSet<T: Ord>,
+	#[cfg(feature = "hashbrown")]
+	HashbrownSet<T: Eq Hash>,
+	#[cfg(feature = "indexmap")]
+	IndexSet<T: Eq Hash>
+}
+
+macro_rules! map_metadata_recursively_map {
+	($($(#[$cfg:meta])? $ty:ident < K: $($bound:ident)+ >),*) => {
+		$(
+			$(#[$cfg])?
+			impl<K: $($bound +)*, T, M, N> MapMetadataRecursively<M, N> for $ty<K, T>
+			where
+				T: MapMetadataRecursively<M, N>,
+			{
+				type Output = $ty<K, T::Output>;
+
+				fn map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Self::Output
+				where
+					F: FnMut(M) -> N,
+				{
+					self.into_iter()
+						.map(|(k, t)| (k, t.map_metadata_recursively_mut_ref(f)))
+						.collect()
+				}
+			}
+		)*
+	};
+}
+
+map_metadata_recursively_map! {
+	HashMap<K: Eq Hash>,
+	BTreeMap<K: Ord>,
+	#[cfg(feature = "hashbrown")]
+	HashbrownMap<K: Eq Hash>,
+	#[cfg(feature = "indexmap")]
+	IndexMap<K: Eq Hash>
+}
+
 /// Provides a function that tries to map the metadata inside a recursive data structure.
 pub trait TryMapMetadataRecursively<M, N, E>: Sized {
 	type Output;
@@ -440,6 +1265,171 @@ where
 	}
 }
 
+impl<T, M, N, E> TryMapMetadataRecursively<M, N, E> for Box<T>
+where
+	T: TryMapMetadataRecursively<M, N, E>,
+{
+	type Output = Box<T::Output>;
+
+	#[inline(always)]
+	fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+	where
+		F: FnMut(M) -> Result<N, E>,
+	{
+		Ok(Box::new((*self).try_map_metadata_recursively_mut_ref(f)?))
+	}
+}
+
+impl<T, M, N, E> TryMapMetadataRecursively<M, N, E> for Rc<T>
+where
+	T: Clone + TryMapMetadataRecursively<M, N, E>,
+{
+	type Output = Rc<T::Output>;
+
+	/// Tries to map the metadata of the shared value.
+	///
+	/// The value is cloned if it is shared.
+	#[inline(always)]
+	fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+	where
+		F: FnMut(M) -> Result<N, E>,
+	{
+		Ok(Rc::new(
+			Rc::unwrap_or_clone(self).try_map_metadata_recursively_mut_ref(f)?,
+		))
+	}
+}
+
+impl<T, M, N, E> TryMapMetadataRecursively<M, N, E> for Arc<T>
+where
+	T: Clone + TryMapMetadataRecursively<M, N, E>,
+{
+	type Output = Arc<T::Output>;
+
+	/// Tries to map the metadata of the shared value.
+	///
+	/// The value is cloned if it is shared.
+	#[inline(always)]
+	fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+	where
+		F: FnMut(M) -> Result<N, E>,
+	{
+		Ok(Arc::new(
+			Arc::unwrap_or_clone(self).try_map_metadata_recursively_mut_ref(f)?,
+		))
+	}
+}
+
+impl<T, M, N, E> TryMapMetadataRecursively<M, N, E> for Option<T>
+where
+	T: TryMapMetadataRecursively<M, N, E>,
+{
+	type Output = Option<T::Output>;
+
+	#[inline(always)]
+	fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+	where
+		F: FnMut(M) -> Result<N, E>,
+	{
+		self.map(|t| t.try_map_metadata_recursively_mut_ref(f))
+			.transpose()
+	}
+}
+
+macro_rules! try_map_metadata_recursively_tuple {
+	($($t:ident),*) => {
+		impl<$($t,)* M, N, E> TryMapMetadataRecursively<M, N, E> for ($($t,)*)
+		where
+			$($t: TryMapMetadataRecursively<M, N, E>,)*
+		{
+			type Output = ($($t::Output,)*);
+
+			#[allow(non_snake_case)]
+			#[inline(always)]
+			fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+			where
+				F: FnMut(M) -> Result<N, E>,
+			{
+				let ($($t,)*) = self;
+				Ok(($($t.try_map_metadata_recursively_mut_ref(f)?,)*))
+			}
+		}
+	};
+}
+
+try_map_metadata_recursively_tuple!(A);
+try_map_metadata_recursively_tuple!(A, B);
+try_map_metadata_recursively_tuple!(A, B, C);
+try_map_metadata_recursively_tuple!(A, B, C, D);
+try_map_metadata_recursively_tuple!(A, B, C, D, G);
+try_map_metadata_recursively_tuple!(A, B, C, D, G, H);
+
+macro_rules! try_map_metadata_recursively_collection {
+	($($(#[$cfg:meta])? $ty:ident < T $(: $($bound:ident)+)? >),*) => {
+		$(
+			$(#[$cfg])?
+			impl<T, M, N, E> TryMapMetadataRecursively<M, N, E> for $ty<T>
+			where
+				T: TryMapMetadataRecursively<M, N, E>,
+				$(T::Output: $($bound +)*)?
+			{
+				type Output = $ty<T::Output>;
+
+				fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+				where
+					F: FnMut(M) -> Result<N, E>,
+				{
+					self.into_iter()
+						.map(|t| t.try_map_metadata_recursively_mut_ref(f))
+						.collect()
+				}
+			}
+		)*
+	};
+}
+
+try_map_metadata_recursively_collection! {
+	Vec<T>,
+	HashSet<T: Eq Hash>,
+	BTreeSet<T: Ord>,
+	#[cfg(feature = "hashbrown")]
+	HashbrownSet<T: Eq Hash>,
+	#[cfg(feature = "indexmap")]
+	IndexSet<T: Eq Hash>
+}
+
+macro_rules! try_map_metadata_recursively_map {
+	($($(#[$cfg:meta])? $ty:ident < K: $($bound:ident)+ >),*) => {
+		$(
+			$(#[$cfg])?
+			impl<K: $($bound +)*, T, M, N, E> TryMapMetadataRecursively<M, N, E> for $ty<K, T>
+			where
+				T: TryMapMetadataRecursively<M, N, E>,
+			{
+				type Output = $ty<K, T::Output>;
+
+				fn try_map_metadata_recursively_mut_ref<F>(self, f: &mut F) -> Result<Self::Output, E>
+				where
+					F: FnMut(M) -> Result<N, E>,
+				{
+					self.into_iter()
+						.map(|(k, t)| Ok((k, t.try_map_metadata_recursively_mut_ref(f)?)))
+						.collect()
+				}
+			}
+		)*
+	};
+}
+
+try_map_metadata_recursively_map! {
+	HashMap<K: Eq Hash>,
+	BTreeMap<K: Ord>,
+	#[cfg(feature = "hashbrown")]
+	HashbrownMap<K: Eq Hash>,
+	#[cfg(feature = "indexmap")]
+	IndexMap<K: Eq Hash>
+}
+
 /// Provides a transposition function from `Option<Meta<T, M>>` to `Meta<Option<T>, M>`.
 pub trait MetaTranspose {
 	/// Located value type.
@@ -468,6 +1458,104 @@ impl<T, M> MetaTranspose for Option<Meta<T, M>> {
 	}
 }
 
+/// Provides accessors and combinators for `Option<Meta<T, M>>`.
+pub trait OptionMetaExt {
+	/// Located value type.
+	type Value;
+
+	/// Metadata type.
+	type Metadata;
+
+	/// Returns a reference to the value, if any.
+	fn value(&self) -> Option<&Self::Value>;
+
+	/// Returns a reference to the metadata, if any.
+	fn metadata(&self) -> Option<&Self::Metadata>;
+
+	/// Unwraps the value and discards its metadata.
+	fn into_value(self) -> Option<Self::Value>;
+
+	/// Discards the value and returns its metadata.
+	fn into_metadata(self) -> Option<Self::Metadata>;
+
+	/// Maps the value, keeping its metadata.
+	fn map_value<U>(self, f: impl FnOnce(Self::Value) -> U) -> Option<Meta<U, Self::Metadata>>;
+
+	/// Maps the metadata, keeping the value.
+	fn map_metadata<N>(self, f: impl FnOnce(Self::Metadata) -> N) -> Option<Meta<Self::Value, N>>;
+
+	/// Returns the contained value, or `default` located at `metadata`.
+	///
+	/// ```
+	/// use locspan::{Meta, OptionMetaExt, Span};
+	///
+	/// let missing: Option<Meta<u32, Span>> = None;
+	/// assert_eq!(missing.unwrap_or_at(Span::new(4, 4), 0), Meta(0, Span::new(4, 4)));
+	/// ```
+	fn unwrap_or_at(
+		self,
+		metadata: Self::Metadata,
+		default: Self::Value,
+	) -> Meta<Self::Value, Self::Metadata>;
+
+	/// Returns the contained value, or the default value located at the
+	/// result of the `metadata` function.
+	fn unwrap_or_default_at(
+		self,
+		metadata: impl FnOnce() -> Self::Metadata,
+	) -> Meta<Self::Value, Self::Metadata>
+	where
+		Self::Value: Default;
+}
+
+impl<T, M> OptionMetaExt for Option<Meta<T, M>> {
+	type Value = T;
+	type Metadata = M;
+
+	#[inline(always)]
+	fn value(&self) -> Option<&T> {
+		self.as_ref().map(Meta::value)
+	}
+
+	#[inline(always)]
+	fn metadata(&self) -> Option<&M> {
+		self.as_ref().map(Meta::metadata)
+	}
+
+	#[inline(always)]
+	fn into_value(self) -> Option<T> {
+		self.map(Meta::into_value)
+	}
+
+	#[inline(always)]
+	fn into_metadata(self) -> Option<M> {
+		self.map(Meta::into_metadata)
+	}
+
+	#[inline(always)]
+	fn map_value<U>(self, f: impl FnOnce(T) -> U) -> Option<Meta<U, M>> {
+		self.map(|m| m.map(f))
+	}
+
+	#[inline(always)]
+	fn map_metadata<N>(self, f: impl FnOnce(M) -> N) -> Option<Meta<T, N>> {
+		self.map(|m| m.map_metadata(f))
+	}
+
+	#[inline(always)]
+	fn unwrap_or_at(self, metadata: M, default: T) -> Meta<T, M> {
+		self.unwrap_or(Meta(default, metadata))
+	}
+
+	#[inline(always)]
+	fn unwrap_or_default_at(self, metadata: impl FnOnce() -> M) -> Meta<T, M>
+	where
+		T: Default,
+	{
+		self.unwrap_or_else(|| Meta(T::default(), metadata()))
+	}
+}
+
 /// Locates the error of a `Result<T, E>`.
 pub trait ErrAt {
 	/// Success type.
@@ -479,6 +1567,18 @@ pub trait ErrAt {
 	/// Changes a `Result<Self::Value, Self::Error>` into a `Result<Self::Value, Meta<Self::Error, M>>` by wrapping
 	/// any eventual error using the result of the `metadata` function.
 	fn err_at<M>(self, metadata: impl FnOnce() -> M) -> Result<Self::Value, Meta<Self::Error, M>>;
+
+	/// Changes a `Result<Self::Value, Self::Error>` into a `Result<Self::Value, Meta<Self::Error, M>>` by wrapping
+	/// any eventual error using the given `metadata`.
+	///
+	/// Unlike [`err_at`](Self::err_at), the metadata is eagerly evaluated.
+	#[inline(always)]
+	fn err_at_value<M>(self, metadata: M) -> Result<Self::Value, Meta<Self::Error, M>>
+	where
+		Self: Sized,
+	{
+		self.err_at(|| metadata)
+	}
 }
 
 impl<T, E> ErrAt for Result<T, E> {
@@ -494,6 +1594,48 @@ impl<T, E> ErrAt for Result<T, E> {
 	}
 }
 
+/// Locates the success value of a `Result<T, E>`.
+pub trait OkAt {
+	/// Success type.
+	type Value;
+
+	/// Error type.
+	type Error;
+
+	/// Changes a `Result<Self::Value, Self::Error>` into a `Result<Meta<Self::Value, M>, Self::Error>` by wrapping
+	/// any eventual success value using the given `metadata`.
+	fn ok_at<M>(self, metadata: M) -> Result<Meta<Self::Value, M>, Self::Error>;
+
+	/// Changes a `Result<Self::Value, Self::Error>` into a `Result<Meta<Self::Value, M>, Meta<Self::Error, M>>` by
+	/// wrapping either the success value or the error using the given `metadata`.
+	///
+	/// ```
+	/// use locspan::{Meta, OkAt, Span};
+	///
+	/// let result: Result<u32, _> = "x".parse::<u32>();
+	/// assert!(matches!(result.at_both(Span::new(0, 1)), Err(Meta(_, span)) if span == Span::new(0, 1)));
+	/// ```
+	fn at_both<M>(self, metadata: M) -> Result<Meta<Self::Value, M>, Meta<Self::Error, M>>;
+}
+
+impl<T, E> OkAt for Result<T, E> {
+	type Value = T;
+	type Error = E;
+
+	#[inline(always)]
+	fn ok_at<M>(self, metadata: M) -> Result<Meta<Self::Value, M>, Self::Error> {
+		self.map(|t| Meta(t, metadata))
+	}
+
+	#[inline(always)]
+	fn at_both<M>(self, metadata: M) -> Result<Meta<Self::Value, M>, Meta<Self::Error, M>> {
+		match self {
+			Ok(t) => Ok(Meta(t, metadata)),
+			Err(e) => Err(Meta(e, metadata)),
+		}
+	}
+}
+
 /// Maps the located error of a `Result<T, Meta<E, F>>`.
 pub trait MapLocErr {
 	/// Success type.
@@ -529,3 +1671,46 @@ impl<T, E, M> MapLocErr for Result<T, Meta<E, M>> {
 		}
 	}
 }
+
+/// Combines the located errors of two `Result<T, Meta<E, M>>`.
+pub trait MergeLocErr<E, M> {
+	/// Success type.
+	type Value;
+
+	/// Pairs the success values of `self` and `other`.
+	///
+	/// If only one of the two fails, its error is returned. If both fail,
+	/// the errors are combined with `combine_errors` into a single error
+	/// covering the merge of both metadata (e.g. the union of both spans).
+	///
+	/// ```
+	/// use locspan::{Meta, MergeLocErr, Span};
+	///
+	/// let a: Result<u32, _> = Err(Meta("expected X".to_string(), Span::new(0, 2)));
+	/// let b: Result<u32, _> = Err(Meta("expected Y".to_string(), Span::new(2, 5)));
+	/// let merged = a.and_merge_err(b, |a, b| format!("{a} or {}", b.trim_start_matches("expected ")));
+	/// assert_eq!(merged, Err(Meta("expected X or Y".to_string(), Span::new(0, 5))));
+	/// ```
+	fn and_merge_err<U>(
+		self,
+		other: Result<U, Meta<E, M>>,
+		combine_errors: impl FnOnce(E, E) -> E,
+	) -> Result<(Self::Value, U), Meta<E, M>>;
+}
+
+impl<T, E, M: Merge> MergeLocErr<E, M> for Result<T, Meta<E, M>> {
+	type Value = T;
+
+	#[inline(always)]
+	fn and_merge_err<U>(
+		self,
+		other: Result<U, Meta<E, M>>,
+		combine_errors: impl FnOnce(E, E) -> E,
+	) -> Result<(T, U), Meta<E, M>> {
+		match (self, other) {
+			(Ok(t), Ok(u)) => Ok((t, u)),
+			(Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e),
+			(Err(a), Err(b)) => Err(a.merge_with(b, combine_errors, M::merge)),
+		}
+	}
+}
@@ -0,0 +1,296 @@
+use crate::{Meta, Spanned};
+use std::iter::FusedIterator;
+
+/// Iterator adapters for iterators of [`Meta`] values.
+///
+/// This trait is implemented for every iterator of `Meta<T, M>`, such as
+/// token streams.
+///
+/// ```
+/// use locspan::{Meta, MetaIteratorExt, Span};
+///
+/// let tokens = vec![Meta('a', Span::new(0, 1)), Meta(' ', Span::new(1, 2)), Meta('b', Span::new(2, 3))];
+///
+/// let spans: Vec<_> = tokens
+///   .iter()
+///   .cloned()
+///   .filter_values(|c| !c.is_whitespace())
+///   .spans()
+///   .collect();
+/// assert_eq!(spans, [Span::new(0, 1), Span::new(2, 3)]);
+///
+/// let chars: String = tokens.into_iter().map_values(|c| c.to_ascii_uppercase()).strip().collect();
+/// assert_eq!(chars, "A B");
+/// ```
+pub trait MetaIteratorExt<T, M>: Sized + Iterator<Item = Meta<T, M>> {
+	/// Maps the value of each item, keeping its metadata.
+	#[inline(always)]
+	fn map_values<U, F: FnMut(T) -> U>(self, f: F) -> MapValues<Self, F> {
+		MapValues { iter: self, f }
+	}
+
+	/// Maps the metadata of each item, keeping its value.
+	#[inline(always)]
+	fn map_metadata<N, F: FnMut(M) -> N>(self, f: F) -> MapMetadata<Self, F> {
+		MapMetadata { iter: self, f }
+	}
+
+	/// Discards the metadata of each item.
+	#[inline(always)]
+	fn strip(self) -> StripMetadata<Self> {
+		StripMetadata { iter: self }
+	}
+
+	/// Only keeps the items whose value satisfies the given predicate.
+	#[inline(always)]
+	fn filter_values<P: FnMut(&T) -> bool>(self, predicate: P) -> FilterValues<Self, P> {
+		FilterValues {
+			iter: self,
+			predicate,
+		}
+	}
+
+	/// Returns the span of each item metadata.
+	#[inline(always)]
+	fn spans(self) -> MetadataSpans<Self>
+	where
+		M: Spanned,
+	{
+		MetadataSpans { iter: self }
+	}
+
+	/// Separates the values from their metadata.
+	///
+	/// ```
+	/// use locspan::{Meta, MetaIteratorExt, Span};
+	///
+	/// let tokens = vec![Meta('a', Span::new(0, 1)), Meta('b', Span::new(2, 3))];
+	/// let (values, spans) = tokens.into_iter().unzip_meta();
+	/// assert_eq!(values, ['a', 'b']);
+	/// assert_eq!(spans, [Span::new(0, 1), Span::new(2, 3)]);
+	/// ```
+	#[inline(always)]
+	fn unzip_meta(self) -> (Vec<T>, Vec<M>) {
+		self.map(Meta::into_parts).unzip()
+	}
+}
+
+impl<T, M, I: Iterator<Item = Meta<T, M>>> MetaIteratorExt<T, M> for I {}
+
+/// Collects the values and metadata of an iterator of [`Meta`] items into
+/// two separate collections.
+///
+/// ```
+/// use locspan::{Meta, Span};
+///
+/// let tokens = vec![Meta('a', Span::new(0, 1)), Meta('b', Span::new(2, 3))];
+/// let Meta(values, spans): Meta<String, Vec<Span>> = tokens.into_iter().collect();
+/// assert_eq!(values, "ab");
+/// assert_eq!(spans, [Span::new(0, 1), Span::new(2, 3)]);
+/// ```
+impl<T, M, A: Default + Extend<T>, B: Default + Extend<M>> FromIterator<Meta<T, M>> for Meta<A, B> {
+	fn from_iter<I: IntoIterator<Item = Meta<T, M>>>(iter: I) -> Self {
+		let mut values = A::default();
+		let mut metadata = B::default();
+		for Meta(t, m) in iter {
+			values.extend(Some(t));
+			metadata.extend(Some(m));
+		}
+
+		Meta(values, metadata)
+	}
+}
+
+/// Iterator mapping the values of [`Meta`] items.
+///
+/// Created by the [`MetaIteratorExt::map_values`] method.
+#[derive(Clone, Debug)]
+pub struct MapValues<I, F> {
+	iter: I,
+	f: F,
+}
+
+impl<T, U, M, I: Iterator<Item = Meta<T, M>>, F: FnMut(T) -> U> Iterator for MapValues<I, F> {
+	type Item = Meta<U, M>;
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|m| m.map(&mut self.f))
+	}
+}
+
+impl<T, U, M, I: DoubleEndedIterator<Item = Meta<T, M>>, F: FnMut(T) -> U> DoubleEndedIterator
+	for MapValues<I, F>
+{
+	#[inline(always)]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(|m| m.map(&mut self.f))
+	}
+}
+
+impl<T, U, M, I: ExactSizeIterator<Item = Meta<T, M>>, F: FnMut(T) -> U> ExactSizeIterator
+	for MapValues<I, F>
+{
+}
+
+impl<T, U, M, I: FusedIterator<Item = Meta<T, M>>, F: FnMut(T) -> U> FusedIterator
+	for MapValues<I, F>
+{
+}
+
+/// Iterator mapping the metadata of [`Meta`] items.
+///
+/// Created by the [`MetaIteratorExt::map_metadata`] method.
+#[derive(Clone, Debug)]
+pub struct MapMetadata<I, F> {
+	iter: I,
+	f: F,
+}
+
+impl<T, M, N, I: Iterator<Item = Meta<T, M>>, F: FnMut(M) -> N> Iterator for MapMetadata<I, F> {
+	type Item = Meta<T, N>;
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|m| m.map_metadata(&mut self.f))
+	}
+}
+
+impl<T, M, N, I: DoubleEndedIterator<Item = Meta<T, M>>, F: FnMut(M) -> N> DoubleEndedIterator
+	for MapMetadata<I, F>
+{
+	#[inline(always)]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(|m| m.map_metadata(&mut self.f))
+	}
+}
+
+impl<T, M, N, I: ExactSizeIterator<Item = Meta<T, M>>, F: FnMut(M) -> N> ExactSizeIterator
+	for MapMetadata<I, F>
+{
+}
+
+impl<T, M, N, I: FusedIterator<Item = Meta<T, M>>, F: FnMut(M) -> N> FusedIterator
+	for MapMetadata<I, F>
+{
+}
+
+/// Iterator discarding the metadata of [`Meta`] items.
+///
+/// Created by the [`MetaIteratorExt::strip`] method.
+#[derive(Clone, Debug)]
+pub struct StripMetadata<I> {
+	iter: I,
+}
+
+impl<T, M, I: Iterator<Item = Meta<T, M>>> Iterator for StripMetadata<I> {
+	type Item = T;
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(Meta::into_value)
+	}
+}
+
+impl<T, M, I: DoubleEndedIterator<Item = Meta<T, M>>> DoubleEndedIterator for StripMetadata<I> {
+	#[inline(always)]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(Meta::into_value)
+	}
+}
+
+impl<T, M, I: ExactSizeIterator<Item = Meta<T, M>>> ExactSizeIterator for StripMetadata<I> {}
+
+impl<T, M, I: FusedIterator<Item = Meta<T, M>>> FusedIterator for StripMetadata<I> {}
+
+/// Iterator filtering [`Meta`] items by value.
+///
+/// Created by the [`MetaIteratorExt::filter_values`] method.
+#[derive(Clone, Debug)]
+pub struct FilterValues<I, P> {
+	iter: I,
+	predicate: P,
+}
+
+impl<T, M, I: Iterator<Item = Meta<T, M>>, P: FnMut(&T) -> bool> Iterator for FilterValues<I, P> {
+	type Item = Meta<T, M>;
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, self.iter.size_hint().1)
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		let predicate = &mut self.predicate;
+		self.iter.find(|m| predicate(m.value()))
+	}
+}
+
+impl<T, M, I: DoubleEndedIterator<Item = Meta<T, M>>, P: FnMut(&T) -> bool> DoubleEndedIterator
+	for FilterValues<I, P>
+{
+	#[inline(always)]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let predicate = &mut self.predicate;
+		self.iter.rfind(|m| predicate(m.value()))
+	}
+}
+
+impl<T, M, I: FusedIterator<Item = Meta<T, M>>, P: FnMut(&T) -> bool> FusedIterator
+	for FilterValues<I, P>
+{
+}
+
+/// Iterator over the spans of [`Meta`] items metadata.
+///
+/// Created by the [`MetaIteratorExt::spans`] method.
+#[derive(Clone, Debug)]
+pub struct MetadataSpans<I> {
+	iter: I,
+}
+
+impl<T, M: Spanned, I: Iterator<Item = Meta<T, M>>> Iterator for MetadataSpans<I> {
+	type Item = M::Span;
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|m| m.metadata().span())
+	}
+}
+
+impl<T, M: Spanned, I: DoubleEndedIterator<Item = Meta<T, M>>> DoubleEndedIterator
+	for MetadataSpans<I>
+{
+	#[inline(always)]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(|m| m.metadata().span())
+	}
+}
+
+impl<T, M: Spanned, I: ExactSizeIterator<Item = Meta<T, M>>> ExactSizeIterator
+	for MetadataSpans<I>
+{
+}
+
+impl<T, M: Spanned, I: FusedIterator<Item = Meta<T, M>>> FusedIterator for MetadataSpans<I> {}
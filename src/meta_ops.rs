@@ -0,0 +1,53 @@
+use crate::{Merge, Meta};
+use std::ops;
+
+macro_rules! binary_op {
+	($($tr:ident :: $f:ident),*) => {
+		$(
+			impl<T: ops::$tr<U>, U, M: Merge> ops::$tr<Meta<U, M>> for Meta<T, M> {
+				type Output = Meta<T::Output, M>;
+
+				/// Applies the operator to the values, merging the metadata of
+				/// both operands.
+				#[inline(always)]
+				fn $f(self, other: Meta<U, M>) -> Self::Output {
+					Meta(ops::$tr::$f(self.0, other.0), self.1.merge(other.1))
+				}
+			}
+		)*
+	};
+}
+
+binary_op! {
+	Add::add,
+	Sub::sub,
+	Mul::mul,
+	Div::div,
+	Rem::rem,
+	BitAnd::bitand,
+	BitOr::bitor,
+	BitXor::bitxor,
+	Shl::shl,
+	Shr::shr
+}
+
+macro_rules! unary_op {
+	($($tr:ident :: $f:ident),*) => {
+		$(
+			impl<T: ops::$tr, M> ops::$tr for Meta<T, M> {
+				type Output = Meta<T::Output, M>;
+
+				/// Applies the operator to the value, keeping its metadata.
+				#[inline(always)]
+				fn $f(self) -> Self::Output {
+					Meta(ops::$tr::$f(self.0), self.1)
+				}
+			}
+		)*
+	};
+}
+
+unary_op! {
+	Neg::neg,
+	Not::not
+}
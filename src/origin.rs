@@ -0,0 +1,131 @@
+use crate::{Location, Span};
+use std::fmt;
+
+/// Origin of a syntax element.
+///
+/// Used as file identifier for elements that may not come from a source
+/// file, such as nodes synthesized by a compiler.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Origin<F> {
+	/// Element found in the given source file.
+	File(F),
+
+	/// Synthesized element, with a tag describing its origin (e.g.
+	/// `"builtin"`).
+	Synthetic(&'static str),
+
+	/// Unknown origin.
+	Unknown,
+}
+
+impl<F> Origin<F> {
+	/// Checks if the element comes from a source file.
+	#[inline(always)]
+	pub fn is_file(&self) -> bool {
+		matches!(self, Self::File(_))
+	}
+
+	/// Returns the source file, if any.
+	#[inline(always)]
+	pub fn file(&self) -> Option<&F> {
+		match self {
+			Self::File(f) => Some(f),
+			_ => None,
+		}
+	}
+
+	/// Returns the source file, if any.
+	#[inline(always)]
+	pub fn into_file(self) -> Option<F> {
+		match self {
+			Self::File(f) => Some(f),
+			_ => None,
+		}
+	}
+
+	/// Borrows the source file.
+	#[inline(always)]
+	pub fn as_ref(&self) -> Origin<&F> {
+		match self {
+			Self::File(f) => Origin::File(f),
+			Self::Synthetic(tag) => Origin::Synthetic(tag),
+			Self::Unknown => Origin::Unknown,
+		}
+	}
+
+	/// Maps the source file.
+	#[inline(always)]
+	pub fn map<G>(self, f: impl FnOnce(F) -> G) -> Origin<G> {
+		match self {
+			Self::File(file) => Origin::File(f(file)),
+			Self::Synthetic(tag) => Origin::Synthetic(tag),
+			Self::Unknown => Origin::Unknown,
+		}
+	}
+}
+
+impl<F> From<F> for Origin<F> {
+	#[inline(always)]
+	fn from(file: F) -> Self {
+		Self::File(file)
+	}
+}
+
+impl<F: fmt::Display> fmt::Display for Origin<F> {
+	/// Displays the source file, or the synthetic tag as `<tag>`, or
+	/// `<unknown>`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::File(file) => file.fmt(f),
+			Self::Synthetic(tag) => write!(f, "<{tag}>"),
+			Self::Unknown => write!(f, "<unknown>"),
+		}
+	}
+}
+
+/// Location that may not refer to a source file.
+pub type OptionalLocation<F, S = Span> = Location<Origin<F>, S>;
+
+impl<F, S: Default> Location<Origin<F>, S> {
+	/// Creates an unknown location.
+	#[inline(always)]
+	pub fn unknown() -> Self {
+		Self::new(Origin::Unknown, S::default())
+	}
+
+	/// Creates the location of a synthesized element, with a tag describing
+	/// its origin.
+	///
+	/// ```
+	/// use locspan::{Location, OptionalLocation};
+	///
+	/// let location: OptionalLocation<&str> = Location::synthetic("builtin");
+	/// assert_eq!(location.to_string(), "<builtin>:0..0");
+	/// ```
+	#[inline(always)]
+	pub fn synthetic(tag: &'static str) -> Self {
+		Self::new(Origin::Synthetic(tag), S::default())
+	}
+}
+
+impl<F, S> Location<Origin<F>, S> {
+	/// Checks if the location refers to a source file.
+	#[inline(always)]
+	pub fn is_known(&self) -> bool {
+		self.file().is_file()
+	}
+
+	/// Returns the location in its source file, if any.
+	#[inline(always)]
+	pub fn known(self) -> Option<Location<F, S>> {
+		let (origin, span) = self.into_parts();
+		origin.into_file().map(|file| Location::new(file, span))
+	}
+}
+
+impl<F, S> From<Location<F, S>> for Location<Origin<F>, S> {
+	#[inline(always)]
+	fn from(location: Location<F, S>) -> Self {
+		location.map_file(Origin::File)
+	}
+}
@@ -0,0 +1,60 @@
+use crate::Location;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+impl<F: AsRef<Path>, S: Clone> Location<F, S> {
+	/// Returns the location with its file path made relative to `base`.
+	///
+	/// If the path is not inside `base`, it is left unchanged.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	/// use std::path::Path;
+	///
+	/// let location = Location::new(Path::new("/home/me/project/src/main.rs"), Span::new(0, 1));
+	/// let relative = location.relativize("/home/me/project");
+	/// assert_eq!(relative.file(), Path::new("src/main.rs"));
+	/// ```
+	pub fn relativize(&self, base: impl AsRef<Path>) -> Location<PathBuf, S> {
+		let path = self.file().as_ref();
+		let path = path.strip_prefix(base).unwrap_or(path);
+		Location::new(path.to_path_buf(), self.span())
+	}
+
+	/// Returns the location with its file path canonicalized.
+	///
+	/// See [`std::fs::canonicalize`].
+	pub fn canonicalized(&self) -> io::Result<Location<PathBuf, S>> {
+		let path = std::fs::canonicalize(self.file())?;
+		Ok(Location::new(path, self.span()))
+	}
+}
+
+impl<F: AsRef<Path>, S> Location<F, S> {
+	/// Returns a value displaying the location as `file:span`, with the file
+	/// path made relative to the current directory when it is inside it.
+	///
+	/// This keeps diagnostics readable in command line tools.
+	#[inline(always)]
+	pub fn display_relative(&self) -> DisplayRelative<'_, F, S> {
+		DisplayRelative(self)
+	}
+}
+
+/// Displays a location with a path relative to the current directory.
+///
+/// Created by the [`Location::display_relative`] method.
+pub struct DisplayRelative<'a, F, S>(&'a Location<F, S>);
+
+impl<F: AsRef<Path>, S: fmt::Display> fmt::Display for DisplayRelative<'_, F, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let path = self.0.file().as_ref();
+		let cwd = std::env::current_dir().ok();
+		let path = cwd
+			.as_deref()
+			.and_then(|cwd| path.strip_prefix(cwd).ok())
+			.unwrap_or(path);
+		write!(f, "{}:{}", path.display(), self.0.span_ref())
+	}
+}
@@ -0,0 +1,63 @@
+use crate::{ColumnEncoding, LineColSpan, LineIndex, Location, Position, Span};
+
+impl From<proc_macro2::LineColumn> for Position {
+	/// Converts a `proc_macro2` line/column (1-based line, 0-based column in
+	/// characters) into a 0-based position, with the column in characters.
+	#[inline(always)]
+	fn from(lc: proc_macro2::LineColumn) -> Self {
+		Self::new(lc.line.saturating_sub(1), lc.column)
+	}
+}
+
+impl From<proc_macro2::Span> for LineColSpan {
+	/// Converts a `proc_macro2` span into a line/column span.
+	///
+	/// Columns are counted in characters, as reported by `proc_macro2`
+	/// (see [`ColumnEncoding::Utf32`]).
+	///
+	/// ```
+	/// use locspan::{LineColSpan, Position};
+	///
+	/// let tokens: proc_macro2::TokenStream = "let x =\n  foo;".parse().unwrap();
+	/// let foo = tokens.into_iter().nth(3).unwrap();
+	/// assert_eq!(
+	///   LineColSpan::from(foo.span()),
+	///   LineColSpan::new(Position::new(1, 2), Position::new(1, 5))
+	/// );
+	/// ```
+	#[inline(always)]
+	fn from(span: proc_macro2::Span) -> Self {
+		Self::new(span.start().into(), span.end().into())
+	}
+}
+
+impl LineIndex<'_> {
+	/// Resolves a `proc_macro2` span into a byte span of the indexed source.
+	///
+	/// Returns `None` if the span is not in the indexed source text.
+	#[inline(always)]
+	pub fn proc_macro_span(&self, span: proc_macro2::Span) -> Option<Span> {
+		self.span_with(span.into(), ColumnEncoding::Utf32)
+	}
+}
+
+impl<F> Location<F> {
+	/// Creates a location in `file` from a `proc_macro2` span, using the line
+	/// index of the file's source text to resolve it into byte offsets.
+	///
+	/// Returns `None` if the span is not in the indexed source text.
+	#[inline(always)]
+	pub fn from_proc_macro(file: F, span: proc_macro2::Span, index: &LineIndex) -> Option<Self> {
+		Some(Self::new(file, index.proc_macro_span(span)?))
+	}
+}
+
+impl<F> Location<F, LineColSpan> {
+	/// Creates a line/column location in `file` from a `proc_macro2` span.
+	///
+	/// Columns are counted in characters, as reported by `proc_macro2`.
+	#[inline(always)]
+	pub fn from_proc_macro(file: F, span: proc_macro2::Span) -> Self {
+		Self::new(file, span.into())
+	}
+}
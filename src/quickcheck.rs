@@ -0,0 +1,47 @@
+use crate::{Location, Meta, Span};
+use quickcheck::{Arbitrary, Gen};
+
+/// Generates spans respecting the `start <= end` invariant.
+impl Arbitrary for Span {
+	fn arbitrary(g: &mut Gen) -> Self {
+		let a = usize::arbitrary(g);
+		let b = usize::arbitrary(g);
+		Self::new(std::cmp::min(a, b), std::cmp::max(a, b))
+	}
+
+	fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+		Box::new(
+			(self.start(), self.len())
+				.shrink()
+				.map(|(start, len)| Self::new(start, start.saturating_add(len))),
+		)
+	}
+}
+
+impl<F: Arbitrary, S: Arbitrary> Arbitrary for Location<F, S> {
+	fn arbitrary(g: &mut Gen) -> Self {
+		Self::new(F::arbitrary(g), S::arbitrary(g))
+	}
+
+	fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+		Box::new(
+			(self.file().clone(), self.span())
+				.shrink()
+				.map(|(file, span)| Self::new(file, span)),
+		)
+	}
+}
+
+impl<T: Arbitrary, M: Arbitrary> Arbitrary for Meta<T, M> {
+	fn arbitrary(g: &mut Gen) -> Self {
+		Self(T::arbitrary(g), M::arbitrary(g))
+	}
+
+	fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+		Box::new(
+			(self.0.clone(), self.1.clone())
+				.shrink()
+				.map(|(t, m)| Self(t, m)),
+		)
+	}
+}
@@ -0,0 +1,103 @@
+use crate::{Meta, Span};
+use std::fmt;
+use std::time::Instant;
+
+/// Error returned by [`relex`] when its time budget is exhausted.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BudgetExceeded;
+
+impl fmt::Display for BudgetExceeded {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "re-lexing time budget exceeded")
+	}
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// Incrementally re-lexes a token buffer after an edit of its source.
+///
+/// The `damaged` span is the span, in the old source, of the text that was
+/// replaced by `new_len` bytes to obtain the new `source`.
+/// Lexing restarts from the first token touching the damaged span, and stops
+/// as soon as the lexer produces a token identical (by span) to an old token
+/// following the edit. The new tokens are spliced into the buffer and the
+/// spans of all the following tokens are shifted accordingly.
+///
+/// The `lexer` function is called with the new source and a byte position,
+/// and must return the next token starting at or after this position, or
+/// `None` at the end of the source. It must always make progress, and its
+/// output must only depend on the position it is given.
+///
+/// If a `deadline` is given and reached before the end of re-lexing, the
+/// buffer is left unchanged and [`BudgetExceeded`] is returned, so the caller
+/// can fall back to a full re-lexing at a more convenient time.
+///
+/// On success, returns the span of the re-lexed region in the new source.
+pub fn relex<T>(
+	tokens: &mut Vec<Meta<T, Span>>,
+	source: &str,
+	damaged: Span,
+	new_len: usize,
+	deadline: Option<Instant>,
+	mut lexer: impl FnMut(&str, usize) -> Option<Meta<T, Span>>,
+) -> Result<Span, BudgetExceeded> {
+	let new_end = damaged.start() + new_len;
+	let shift = |span: Span| {
+		Span::new(
+			span.start() - damaged.end() + new_end,
+			span.end() - damaged.end() + new_end,
+		)
+	};
+
+	// Tokens touching the damaged span are damaged as well, since the edit
+	// may merge them with the new text.
+	let first = tokens.partition_point(|t| t.1.end() < damaged.start());
+	let undamaged = std::cmp::max(
+		first,
+		tokens.partition_point(|t| t.1.start() <= damaged.end()),
+	);
+
+	let start = match tokens.get(first) {
+		Some(t) => std::cmp::min(t.1.start(), damaged.start()),
+		None => damaged.start(),
+	};
+
+	let mut new_tokens = Vec::new();
+	let mut end = start;
+	let mut resync = undamaged;
+	loop {
+		if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+			return Err(BudgetExceeded);
+		}
+
+		match lexer(source, end) {
+			Some(token) => {
+				while resync < tokens.len() && shift(tokens[resync].1).start() < token.1.start() {
+					resync += 1
+				}
+
+				if token.1.start() >= new_end
+					&& resync < tokens.len()
+					&& shift(tokens[resync].1) == token.1
+				{
+					break;
+				}
+
+				end = token.1.end();
+				new_tokens.push(token)
+			}
+			None => {
+				end = source.len();
+				resync = tokens.len();
+				break;
+			}
+		}
+	}
+
+	for token in &mut tokens[resync..] {
+		token.1 = shift(token.1)
+	}
+
+	tokens.splice(first..resync, new_tokens);
+	Ok(Span::new(start, end))
+}
@@ -0,0 +1,223 @@
+//! Structured access to diagnostics for custom renderers.
+//!
+//! The [`RenderDiagnostic`] trait exposes the parts of a diagnostic
+//! (severity, message, labeled regions, notes and suggestions) so that
+//! third-party renderers (GUI, HTML, TUI, etc.) can consume diagnostics
+//! without depending on a particular reporting library.
+use crate::{Location, Span};
+
+#[cfg(feature = "html")]
+mod html;
+
+#[cfg(feature = "html")]
+pub use html::*;
+
+/// Diagnostic severity.
+///
+/// Severities are ordered from the least to the most severe.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Severity {
+	/// Help message.
+	Help,
+
+	/// Note.
+	Note,
+
+	/// Warning.
+	Warning,
+
+	/// Error.
+	Error,
+
+	/// Unexpected bug.
+	Bug,
+}
+
+impl Severity {
+	/// Returns the lowercase name of the severity.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Help => "help",
+			Self::Note => "note",
+			Self::Warning => "warning",
+			Self::Error => "error",
+			Self::Bug => "bug",
+		}
+	}
+}
+
+/// Label style.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum LabelStyle {
+	/// Describes the primary cause of a diagnostic.
+	Primary,
+
+	/// Provides additional context for a diagnostic.
+	Secondary,
+}
+
+/// Labeled location of a diagnostic.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Label<'a, F> {
+	/// Style of the label.
+	pub style: LabelStyle,
+
+	/// Labeled location.
+	pub location: Location<&'a F>,
+
+	/// Message attached to the label, possibly empty.
+	pub message: &'a str,
+}
+
+/// Suggested replacement of the text at a given location.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Suggestion<'a, F> {
+	/// Location of the text to replace.
+	pub location: Location<&'a F>,
+
+	/// Replacement text.
+	pub replacement: &'a str,
+
+	/// Message explaining the suggestion, possibly empty.
+	pub message: &'a str,
+}
+
+/// Label resolved against the source text of its file.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Region<'a, F> {
+	/// Resolved label.
+	pub label: Label<'a, F>,
+
+	/// Labeled text.
+	pub snippet: &'a str,
+
+	/// Span of the full lines containing the labeled text.
+	pub lines: Span,
+
+	/// Text of the full lines containing the labeled text.
+	pub context: &'a str,
+
+	/// Number (starting from 1) of the first line containing the labeled text.
+	pub line_number: usize,
+}
+
+impl<'a, F> Region<'a, F> {
+	/// Resolves the given label against the source text of its file.
+	///
+	/// Returns `None` if the label's span is not a valid span of `source`.
+	pub fn resolve(label: Label<'a, F>, source: &'a str) -> Option<Self> {
+		let span = label.location.span();
+		let snippet = source.get(span.range())?;
+
+		let start = source[..span.start()]
+			.rfind('\n')
+			.map(|i| i + 1)
+			.unwrap_or(0);
+		let end = source[span.end()..]
+			.find('\n')
+			.map(|i| span.end() + i)
+			.unwrap_or(source.len());
+		let lines = Span::new(start, end);
+
+		Some(Self {
+			label,
+			snippet,
+			lines,
+			context: &source[lines],
+			line_number: 1 + source[..start].bytes().filter(|b| *b == b'\n').count(),
+		})
+	}
+}
+
+/// Diagnostic that can be rendered by custom renderers.
+pub trait RenderDiagnostic {
+	/// File identifier type.
+	type File;
+
+	/// Returns the severity of the diagnostic.
+	fn severity(&self) -> Severity;
+
+	/// Returns the code identifying the diagnostic, if any.
+	fn code(&self) -> Option<&str> {
+		None
+	}
+
+	/// Returns the main message of the diagnostic.
+	fn message(&self) -> &str;
+
+	/// Returns the labeled locations of the diagnostic.
+	fn labels(&self) -> Vec<Label<'_, Self::File>>;
+
+	/// Returns the notes attached to the diagnostic.
+	fn notes(&self) -> Vec<&str> {
+		Vec::new()
+	}
+
+	/// Returns the replacements suggested by the diagnostic.
+	fn suggestions(&self) -> Vec<Suggestion<'_, Self::File>> {
+		Vec::new()
+	}
+
+	/// Resolves the labels of the diagnostic using the given `sources`
+	/// function returning the source text of a file.
+	///
+	/// Labels whose source is unknown or whose span is invalid are skipped.
+	fn regions<'a>(
+		&'a self,
+		sources: impl Fn(&Self::File) -> Option<&'a str>,
+	) -> Vec<Region<'a, Self::File>>
+	where
+		Self: Sized,
+	{
+		self.labels()
+			.into_iter()
+			.filter_map(|label| {
+				let source = sources(label.location.file())?;
+				Region::resolve(label, source)
+			})
+			.collect()
+	}
+}
+
+#[cfg(feature = "reporting")]
+impl<F> RenderDiagnostic for codespan_reporting::diagnostic::Diagnostic<F> {
+	type File = F;
+
+	fn severity(&self) -> Severity {
+		use codespan_reporting::diagnostic::Severity as S;
+		match self.severity {
+			S::Help => Severity::Help,
+			S::Note => Severity::Note,
+			S::Warning => Severity::Warning,
+			S::Error => Severity::Error,
+			S::Bug => Severity::Bug,
+		}
+	}
+
+	fn code(&self) -> Option<&str> {
+		self.code.as_deref()
+	}
+
+	fn message(&self) -> &str {
+		&self.message
+	}
+
+	fn labels(&self) -> Vec<Label<'_, F>> {
+		use codespan_reporting::diagnostic::LabelStyle as S;
+		self.labels
+			.iter()
+			.map(|label| Label {
+				style: match label.style {
+					S::Primary => LabelStyle::Primary,
+					S::Secondary => LabelStyle::Secondary,
+				},
+				location: Location::new(&label.file_id, label.range.clone().into()),
+				message: &label.message,
+			})
+			.collect()
+	}
+
+	fn notes(&self) -> Vec<&str> {
+		self.notes.iter().map(String::as_str).collect()
+	}
+}
@@ -0,0 +1,121 @@
+use super::{LabelStyle, RenderDiagnostic};
+use std::fmt::{self, Write};
+
+/// Renders the given diagnostic as an HTML fragment.
+///
+/// The `sources` function must return the source text of a file.
+/// The output uses the following classes so it can be styled with CSS:
+/// `diagnostic`, the severity name (`error`, `warning`, etc.), `message`,
+/// `code`, `labels`, `label`, `primary`, `secondary`, `location`, `notes`
+/// and `suggestions`. Labeled text is highlighted using `<mark>` elements.
+pub fn to_html<'a, D: RenderDiagnostic>(
+	diagnostic: &'a D,
+	sources: impl Fn(&D::File) -> Option<&'a str>,
+) -> String
+where
+	D::File: fmt::Display,
+{
+	let mut html = String::new();
+	write_html(&mut html, diagnostic, sources).unwrap();
+	html
+}
+
+fn write_html<'a, D: RenderDiagnostic>(
+	html: &mut String,
+	diagnostic: &'a D,
+	sources: impl Fn(&D::File) -> Option<&'a str>,
+) -> fmt::Result
+where
+	D::File: fmt::Display,
+{
+	let severity = diagnostic.severity().as_str();
+	write!(html, "<div class=\"diagnostic {severity}\">")?;
+	write!(html, "<p class=\"message\">{severity}")?;
+	if let Some(code) = diagnostic.code() {
+		write!(html, "[<span class=\"code\">{}</span>]", Escape(code))?
+	}
+	write!(html, ": {}</p>", Escape(diagnostic.message()))?;
+
+	let regions = diagnostic.regions(sources);
+	if !regions.is_empty() {
+		html.push_str("<ul class=\"labels\">");
+		for region in regions {
+			let style = match region.label.style {
+				LabelStyle::Primary => "primary",
+				LabelStyle::Secondary => "secondary",
+			};
+
+			let span = region.label.location.span();
+			let start = span.start() - region.lines.start();
+			let end = span.end() - region.lines.start();
+			write!(
+				html,
+				"<li class=\"label {style}\"><span class=\"location\">{}:{}</span><pre><code>{}<mark>{}</mark>{}</code></pre>",
+				Escape(&region.label.location.file().to_string()),
+				region.line_number,
+				Escape(&region.context[..start]),
+				Escape(region.snippet),
+				Escape(&region.context[end..])
+			)?;
+
+			if !region.label.message.is_empty() {
+				write!(
+					html,
+					"<span class=\"message\">{}</span>",
+					Escape(region.label.message)
+				)?
+			}
+
+			html.push_str("</li>")
+		}
+		html.push_str("</ul>")
+	}
+
+	let notes = diagnostic.notes();
+	if !notes.is_empty() {
+		html.push_str("<ul class=\"notes\">");
+		for note in notes {
+			write!(html, "<li>{}</li>", Escape(note))?
+		}
+		html.push_str("</ul>")
+	}
+
+	let suggestions = diagnostic.suggestions();
+	if !suggestions.is_empty() {
+		html.push_str("<ul class=\"suggestions\">");
+		for suggestion in suggestions {
+			write!(
+				html,
+				"<li><span class=\"location\">{}:{}</span> <code>{}</code> {}</li>",
+				Escape(&suggestion.location.file().to_string()),
+				suggestion.location.span().start(),
+				Escape(suggestion.replacement),
+				Escape(suggestion.message)
+			)?
+		}
+		html.push_str("</ul>")
+	}
+
+	html.push_str("</div>");
+	Ok(())
+}
+
+/// Escapes HTML special characters.
+struct Escape<'a>(&'a str);
+
+impl fmt::Display for Escape<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for c in self.0.chars() {
+			match c {
+				'&' => f.write_str("&amp;")?,
+				'<' => f.write_str("&lt;")?,
+				'>' => f.write_str("&gt;")?,
+				'"' => f.write_str("&quot;")?,
+				'\'' => f.write_str("&#39;")?,
+				c => f.write_char(c)?,
+			}
+		}
+
+		Ok(())
+	}
+}
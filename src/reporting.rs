@@ -1,6 +1,10 @@
-use crate::Location;
+use crate::{Location, LocationStack, Origin, SourceAccess, SourceId};
+use codespan_reporting::files::SimpleFile;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
 
-impl<F: Clone> Location<F> {
+impl<F: Clone, S: Clone + Into<Range<usize>>> Location<F, S> {
 	#[inline(always)]
 	pub fn as_primary_label(&self) -> codespan_reporting::diagnostic::Label<F> {
 		codespan_reporting::diagnostic::Label::primary(self.file().clone(), self.span())
@@ -12,7 +16,7 @@ impl<F: Clone> Location<F> {
 	}
 }
 
-impl<F> Location<F> {
+impl<F, S: Into<Range<usize>>> Location<F, S> {
 	#[inline(always)]
 	pub fn into_primary_label(self) -> codespan_reporting::diagnostic::Label<F> {
 		let (file, span) = self.into_parts();
@@ -25,3 +29,301 @@ impl<F> Location<F> {
 		codespan_reporting::diagnostic::Label::secondary(file, span)
 	}
 }
+
+impl<F: Clone, S: Clone + Into<Range<usize>>> LocationStack<F, S> {
+	/// Returns the labels of the chain: a primary label for the innermost
+	/// location, followed by a secondary label for each location it was
+	/// expanded from, with the message "expanded from here".
+	pub fn as_labels(&self) -> Vec<codespan_reporting::diagnostic::Label<F>> {
+		self.iter()
+			.enumerate()
+			.map(|(i, location)| {
+				if i == 0 {
+					location.as_primary_label()
+				} else {
+					location
+						.as_secondary_label()
+						.with_message("expanded from here")
+				}
+			})
+			.collect()
+	}
+}
+
+/// Canonical textual representation of a diagnostic.
+///
+/// This provides a deterministic serialization of a
+/// [`Diagnostic`](codespan_reporting::diagnostic::Diagnostic), intended for
+/// golden (snapshot) testing. Unlike the human-readable renderer of
+/// `codespan-reporting`, the output does not depend on the source files, uses
+/// no colors, sorts the labels and normalizes the file paths (using `/` as
+/// separator), so cosmetic changes in the renderer do not invalidate test
+/// expectations.
+///
+/// The output has the following form:
+/// ```text
+/// error[E0001]: message
+///   primary src/main.rs:10..14: label message
+///   secondary src/main.rs:2..5: other label message
+///   = note
+/// ```
+pub struct StableDiagnostic<'a, F>(&'a codespan_reporting::diagnostic::Diagnostic<F>);
+
+impl<'a, F> StableDiagnostic<'a, F> {
+	/// Wraps the given diagnostic.
+	#[inline(always)]
+	pub fn new(diagnostic: &'a codespan_reporting::diagnostic::Diagnostic<F>) -> Self {
+		Self(diagnostic)
+	}
+}
+
+impl<F: fmt::Display> fmt::Display for StableDiagnostic<'_, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use codespan_reporting::diagnostic::{LabelStyle, Severity};
+
+		let severity = match self.0.severity {
+			Severity::Bug => "bug",
+			Severity::Error => "error",
+			Severity::Warning => "warning",
+			Severity::Note => "note",
+			Severity::Help => "help",
+		};
+
+		write!(f, "{severity}")?;
+		if let Some(code) = &self.0.code {
+			write!(f, "[{code}]")?
+		}
+		write!(f, ": ")?;
+		write_lines(f, &self.0.message)?;
+		writeln!(f)?;
+
+		let mut labels: Vec<_> = self
+			.0
+			.labels
+			.iter()
+			.map(|label| {
+				let primary = label.style == LabelStyle::Primary;
+				let file = label.file_id.to_string().replace('\\', "/");
+				(
+					!primary,
+					file,
+					label.range.start,
+					label.range.end,
+					&label.message,
+				)
+			})
+			.collect();
+		labels.sort();
+
+		for (secondary, file, start, end, message) in labels {
+			let style = if secondary { "secondary" } else { "primary" };
+			write!(f, "  {style} {file}:{start}..{end}")?;
+			if !message.is_empty() {
+				write!(f, ": ")?;
+				write_lines(f, message)?
+			}
+			writeln!(f)?
+		}
+
+		for note in &self.0.notes {
+			write!(f, "  = ")?;
+			write_lines(f, note)?;
+			writeln!(f)?
+		}
+
+		Ok(())
+	}
+}
+
+/// Writes the given text, indenting every line after the first one and
+/// normalizing line endings.
+fn write_lines(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+	for (i, line) in text.lines().enumerate() {
+		if i > 0 {
+			write!(f, "\n    ")?
+		}
+		write!(f, "{}", line.trim_end())?
+	}
+
+	Ok(())
+}
+
+/// Source files database supporting synthetic and unknown origins.
+///
+/// Wraps a [`Files`](codespan_reporting::files::Files) database so that
+/// labels can use [`Origin`] file identifiers. Labels attached to synthetic
+/// locations are rendered with the name `<tag>` (e.g. `<builtin>`), and
+/// labels attached to unknown locations with the name `<unknown>`, both
+/// with an empty source.
+pub struct OriginFiles<Fs>(pub Fs);
+
+/// Source of an [`OriginFiles`] file.
+///
+/// Synthetic and unknown origins have an empty source.
+pub struct OriginSource<S>(Option<S>);
+
+impl<S: AsRef<str>> AsRef<str> for OriginSource<S> {
+	fn as_ref(&self) -> &str {
+		match &self.0 {
+			Some(source) => source.as_ref(),
+			None => "",
+		}
+	}
+}
+
+impl<'a, Fs: codespan_reporting::files::Files<'a>> codespan_reporting::files::Files<'a>
+	for OriginFiles<Fs>
+{
+	type FileId = Origin<Fs::FileId>;
+	type Name = Origin<Fs::Name>;
+	type Source = OriginSource<Fs::Source>;
+
+	fn name(&'a self, id: Self::FileId) -> Result<Self::Name, codespan_reporting::files::Error> {
+		match id {
+			Origin::File(id) => self.0.name(id).map(Origin::File),
+			Origin::Synthetic(tag) => Ok(Origin::Synthetic(tag)),
+			Origin::Unknown => Ok(Origin::Unknown),
+		}
+	}
+
+	fn source(
+		&'a self,
+		id: Self::FileId,
+	) -> Result<Self::Source, codespan_reporting::files::Error> {
+		match id {
+			Origin::File(id) => self.0.source(id).map(|s| OriginSource(Some(s))),
+			_ => Ok(OriginSource(None)),
+		}
+	}
+
+	fn line_index(
+		&'a self,
+		id: Self::FileId,
+		byte_index: usize,
+	) -> Result<usize, codespan_reporting::files::Error> {
+		match id {
+			Origin::File(id) => self.0.line_index(id, byte_index),
+			_ => Ok(0),
+		}
+	}
+
+	fn line_range(
+		&'a self,
+		id: Self::FileId,
+		line_index: usize,
+	) -> Result<Range<usize>, codespan_reporting::files::Error> {
+		match id {
+			Origin::File(id) => self.0.line_range(id, line_index),
+			_ if line_index == 0 => Ok(0..0),
+			_ => Err(codespan_reporting::files::Error::LineTooLarge {
+				given: line_index,
+				max: 0,
+			}),
+		}
+	}
+}
+
+/// Source files database indexed by [`SourceId`].
+///
+/// Implements the [`Files`](codespan_reporting::files::Files) trait with
+/// `&SourceId` file identifiers, so that `Location<&SourceId>` labels can be
+/// rendered.
+///
+/// ```
+/// use codespan_reporting::diagnostic::Diagnostic;
+/// use codespan_reporting::term::{self, termcolor::NoColor};
+/// use locspan::{Location, SourceFiles, SourceId, Span};
+///
+/// let mut files = SourceFiles::new();
+/// files.insert(SourceId::Stdin, "let x = 1;".to_string());
+///
+/// let location = Location::new(SourceId::Stdin, Span::new(4, 5));
+/// let diagnostic = Diagnostic::error().with_labels(vec![location.borrow().as_primary_label()]);
+///
+/// let mut output = NoColor::new(Vec::new());
+/// term::emit(&mut output, &term::Config::default(), &files, &diagnostic).unwrap();
+/// assert!(String::from_utf8(output.into_inner()).unwrap().contains("<stdin>:1:5"));
+/// ```
+pub struct SourceFiles<S = String> {
+	files: HashMap<SourceId, SimpleFile<SourceId, S>>,
+}
+
+impl<S> Default for SourceFiles<S> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self {
+			files: HashMap::new(),
+		}
+	}
+}
+
+impl<S> SourceFiles<S> {
+	/// Creates a new empty database.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl<S: AsRef<str>> SourceFiles<S> {
+	/// Adds the given source, replacing any previous source with the same
+	/// identifier.
+	pub fn insert(&mut self, id: SourceId, source: S) {
+		self.files.insert(id.clone(), SimpleFile::new(id, source));
+	}
+
+	/// Returns the source with the given identifier.
+	#[inline(always)]
+	pub fn get(&self, id: &SourceId) -> Option<&S> {
+		self.files.get(id).map(SimpleFile::source)
+	}
+
+	fn file(
+		&self,
+		id: &SourceId,
+	) -> Result<&SimpleFile<SourceId, S>, codespan_reporting::files::Error> {
+		self.files
+			.get(id)
+			.ok_or(codespan_reporting::files::Error::FileMissing)
+	}
+}
+
+impl<S: AsRef<str>> SourceAccess<SourceId> for SourceFiles<S> {
+	#[inline(always)]
+	fn source(&self, file: &SourceId) -> Option<&str> {
+		self.get(file).map(AsRef::as_ref)
+	}
+}
+
+impl<'a, S: 'a + AsRef<str>> codespan_reporting::files::Files<'a> for SourceFiles<S> {
+	type FileId = &'a SourceId;
+	type Name = &'a SourceId;
+	type Source = &'a str;
+
+	fn name(&'a self, id: Self::FileId) -> Result<Self::Name, codespan_reporting::files::Error> {
+		Ok(self.file(id)?.name())
+	}
+
+	fn source(
+		&'a self,
+		id: Self::FileId,
+	) -> Result<Self::Source, codespan_reporting::files::Error> {
+		Ok(self.file(id)?.source().as_ref())
+	}
+
+	fn line_index(
+		&'a self,
+		id: Self::FileId,
+		byte_index: usize,
+	) -> Result<usize, codespan_reporting::files::Error> {
+		self.file(id)?.line_index((), byte_index)
+	}
+
+	fn line_range(
+		&'a self,
+		id: Self::FileId,
+		line_index: usize,
+	) -> Result<Range<usize>, codespan_reporting::files::Error> {
+		self.file(id)?.line_range((), line_index)
+	}
+}
@@ -1,4 +1,5 @@
-use crate::Meta;
+use crate::{FileId, FileRegistry, Location, Meta, Span};
+use std::hash::Hash;
 
 impl<T: serde::Serialize, M> serde::Serialize for Meta<T, M> {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -18,3 +19,237 @@ impl<'de, T: serde::Deserialize<'de>, M: Default> serde::Deserialize<'de> for Me
 		Ok(Self(t, M::default()))
 	}
 }
+
+/// Wrapper serializing both the value and its metadata.
+///
+/// Contrarily to the `Meta` implementation, which only serializes the value
+/// (and restores the default metadata on deserialization), the value is
+/// serialized as a `(value, metadata)` pair, so that the metadata round-trips.
+///
+/// ```
+/// use locspan::{KeepMetadata, Meta, Span};
+///
+/// let token = KeepMetadata(Meta("foo".to_string(), Span::new(2, 5)));
+/// let json = serde_json::to_string(&token).unwrap();
+/// assert_eq!(json, r#"["foo",[2,5]]"#);
+///
+/// let back: KeepMetadata<String, Span> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back, token);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct KeepMetadata<T, M>(pub Meta<T, M>);
+
+impl<T: serde::Serialize, M: serde::Serialize> serde::Serialize for KeepMetadata<T, M> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		self.0.serialize_with_metadata(serializer)
+	}
+}
+
+impl<'de, T: serde::Deserialize<'de>, M: serde::Deserialize<'de>> serde::Deserialize<'de>
+	for KeepMetadata<T, M>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		Meta::deserialize_with_metadata(deserializer).map(Self)
+	}
+}
+
+impl<T, M> Meta<T, M> {
+	/// Serializes the value and its metadata as a `(value, metadata)` pair.
+	///
+	/// Can be used with the `#[serde(serialize_with = "...")]` attribute.
+	pub fn serialize_with_metadata<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: serde::Serialize,
+		M: serde::Serialize,
+		S: serde::Serializer,
+	{
+		serde::Serialize::serialize(&(&self.0, &self.1), serializer)
+	}
+
+	/// Deserializes a value and its metadata from a `(value, metadata)` pair.
+	///
+	/// Can be used with the `#[serde(deserialize_with = "...")]` attribute.
+	pub fn deserialize_with_metadata<'de, D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		T: serde::Deserialize<'de>,
+		M: serde::Deserialize<'de>,
+		D: serde::Deserializer<'de>,
+	{
+		let (t, m) = <(T, M) as serde::Deserialize>::deserialize(deserializer)?;
+		Ok(Self(t, m))
+	}
+}
+
+impl serde::Serialize for Span {
+	/// Serializes the span as a `(start, end)` pair.
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		(self.start(), self.end()).serialize(serializer)
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for Span {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let (start, end) = <(usize, usize)>::deserialize(deserializer)?;
+		Ok(Self::new(start, end))
+	}
+}
+
+impl<F: serde::Serialize, S: serde::Serialize> serde::Serialize for Location<F, S> {
+	/// Serializes the location as a `(file, span)` pair.
+	///
+	/// Use the [`WithoutFile`] or [`InternedFile`] wrappers to control how
+	/// the file is serialized.
+	fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+	where
+		Ser: serde::Serializer,
+	{
+		(self.file(), self.span_ref()).serialize(serializer)
+	}
+}
+
+impl<'de, F: serde::Deserialize<'de>, S: serde::Deserialize<'de>> serde::Deserialize<'de>
+	for Location<F, S>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let (file, span) = <(F, S)>::deserialize(deserializer)?;
+		Ok(Self::new(file, span))
+	}
+}
+
+/// Location wrapper serializing only the span of the location.
+///
+/// When deserialized, the file is set to its default value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct WithoutFile<F, S = Span>(pub Location<F, S>);
+
+impl<F, S: serde::Serialize> serde::Serialize for WithoutFile<F, S> {
+	fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+	where
+		Ser: serde::Serializer,
+	{
+		self.0.span_ref().serialize(serializer)
+	}
+}
+
+impl<'de, F: Default, S: serde::Deserialize<'de>> serde::Deserialize<'de> for WithoutFile<F, S> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let span = S::deserialize(deserializer)?;
+		Ok(Self(Location::new(F::default(), span)))
+	}
+}
+
+/// Location wrapper serializing the file as its interned [`FileId`] in the
+/// given registry.
+///
+/// The result can be deserialized as a `Location<FileId, S>` and resolved
+/// using the same registry (which can itself be serialized).
+///
+/// ```
+/// use locspan::{FileId, FileRegistry, InternedFile, Location, Span};
+///
+/// let mut files = FileRegistry::new();
+/// files.insert("a.txt".to_string());
+/// files.insert("b.txt".to_string());
+///
+/// let location = Location::new("b.txt".to_string(), Span::new(1, 4));
+/// let json = serde_json::to_string(&InternedFile::new(&files, &location)).unwrap();
+/// assert_eq!(json, "[1,[1,4]]");
+///
+/// let interned: Location<FileId> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(files.resolve(interned).file().as_str(), "b.txt");
+/// ```
+pub struct InternedFile<'a, F, S = Span> {
+	registry: &'a FileRegistry<F>,
+	location: &'a Location<F, S>,
+}
+
+impl<'a, F, S> InternedFile<'a, F, S> {
+	/// Wraps the given location, to be serialized using the given registry.
+	#[inline(always)]
+	pub fn new(registry: &'a FileRegistry<F>, location: &'a Location<F, S>) -> Self {
+		Self { registry, location }
+	}
+}
+
+impl<F: Eq + Hash, S: serde::Serialize> serde::Serialize for InternedFile<'_, F, S> {
+	/// Serializes the location as a `(id, span)` pair.
+	///
+	/// Fails if the file is not registered.
+	fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+	where
+		Ser: serde::Serializer,
+	{
+		let id = self
+			.registry
+			.get(self.location.file())
+			.ok_or_else(|| serde::ser::Error::custom("unregistered file"))?;
+		(id, self.location.span_ref()).serialize(serializer)
+	}
+}
+
+impl serde::Serialize for FileId {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		(self.index() as u32).serialize(serializer)
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for FileId {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let index = u32::deserialize(deserializer)?;
+		FileId::from_index(index as usize)
+			.ok_or_else(|| serde::de::Error::custom("file index overflow"))
+	}
+}
+
+impl<F: serde::Serialize> serde::Serialize for FileRegistry<F> {
+	/// Serializes the registry as the sequence of its files, in insertion
+	/// order.
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.collect_seq(self.iter().map(|(_, file)| file))
+	}
+}
+
+impl<'de, F: Clone + Eq + Hash + serde::Deserialize<'de>> serde::Deserialize<'de>
+	for FileRegistry<F>
+{
+	/// Deserializes a registry from the sequence of its files.
+	///
+	/// File ids are preserved, provided the sequence contains no duplicates.
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let mut registry = Self::new();
+		for file in Vec::<F>::deserialize(deserializer)? {
+			registry.insert(file);
+		}
+		Ok(registry)
+	}
+}
@@ -0,0 +1,109 @@
+use crate::Location;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Access to the source text of files.
+pub trait SourceAccess<F: ?Sized> {
+	/// Returns the source text of the given file, if available.
+	fn source(&self, file: &F) -> Option<&str>;
+}
+
+impl<F: ?Sized, T: ?Sized + SourceAccess<F>> SourceAccess<F> for &T {
+	#[inline(always)]
+	fn source(&self, file: &F) -> Option<&str> {
+		T::source(self, file)
+	}
+}
+
+/// A single source text, used for every file.
+impl<F: ?Sized> SourceAccess<F> for str {
+	#[inline(always)]
+	fn source(&self, _file: &F) -> Option<&str> {
+		Some(self)
+	}
+}
+
+/// A single source text, used for every file.
+impl<F: ?Sized> SourceAccess<F> for String {
+	#[inline(always)]
+	fn source(&self, _file: &F) -> Option<&str> {
+		Some(self)
+	}
+}
+
+impl<F: ?Sized + Eq + Hash, K: Eq + Hash + Borrow<F>, S: AsRef<str>> SourceAccess<F>
+	for HashMap<K, S>
+{
+	#[inline(always)]
+	fn source(&self, file: &F) -> Option<&str> {
+		self.get(file).map(AsRef::as_ref)
+	}
+}
+
+impl<F: ?Sized + Ord, K: Ord + Borrow<F>, S: AsRef<str>> SourceAccess<F> for BTreeMap<K, S> {
+	#[inline(always)]
+	fn source(&self, file: &F) -> Option<&str> {
+		self.get(file).map(AsRef::as_ref)
+	}
+}
+
+impl<F> Location<F> {
+	/// Returns the source text at this location.
+	///
+	/// Returns `None` if the source of the file is not available, or if the
+	/// span is not inside the source text.
+	///
+	/// ```
+	/// use locspan::{Location, Span};
+	/// use std::collections::HashMap;
+	///
+	/// let sources = HashMap::from([("main.rs", "let x = 1;\nlet y = x;")]);
+	/// let location = Location::new("main.rs", Span::new(15, 16));
+	/// assert_eq!(location.snippet(&sources), Some("y"));
+	/// assert_eq!(location.snippet_with_context(&sources, 0), Some("let y = x;"));
+	/// ```
+	#[inline(always)]
+	pub fn snippet<'a>(&self, sources: &'a (impl ?Sized + SourceAccess<F>)) -> Option<&'a str> {
+		sources.source(self.file())?.get(self.span().range())
+	}
+
+	/// Returns the source text of the lines covered by this location, with
+	/// `n_lines` additional lines of context before and after them.
+	///
+	/// The returned text does not end with a line break.
+	/// Returns `None` if the source of the file is not available, or if the
+	/// span is not inside the source text.
+	pub fn snippet_with_context<'a>(
+		&self,
+		sources: &'a (impl ?Sized + SourceAccess<F>),
+		n_lines: usize,
+	) -> Option<&'a str> {
+		let source = sources.source(self.file())?;
+		let span = self.span();
+		source.get(span.range())?;
+
+		let mut start = source[..span.start()].rfind('\n').map_or(0, |i| i + 1);
+		for _ in 0..n_lines {
+			if start == 0 {
+				break;
+			}
+			start = source[..start - 1].rfind('\n').map_or(0, |i| i + 1);
+		}
+
+		let mut end = source[span.end()..]
+			.find('\n')
+			.map_or(source.len(), |i| span.end() + i);
+		for _ in 0..n_lines {
+			if end == source.len() {
+				break;
+			}
+			end = source[end + 1..]
+				.find('\n')
+				.map_or(source.len(), |i| end + 1 + i);
+		}
+
+		let text = &source[start..end];
+		Some(text.strip_suffix('\r').unwrap_or(text))
+	}
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Source file identifier.
+///
+/// Covers the kinds of sources most command line tools deal with, and can
+/// be used as file type in [`Location`](crate::Location).
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum SourceId {
+	/// File on the file system.
+	Path(PathBuf),
+
+	/// Named in-memory source (e.g. a command line argument).
+	Inline {
+		/// Name of the source.
+		name: String,
+	},
+
+	/// Standard input.
+	Stdin,
+
+	/// Synthesized source, identified by a number.
+	Synthetic(u32),
+}
+
+impl SourceId {
+	/// Returns the path of the source, if it is a file.
+	#[inline(always)]
+	pub fn as_path(&self) -> Option<&std::path::Path> {
+		match self {
+			Self::Path(path) => Some(path),
+			_ => None,
+		}
+	}
+}
+
+impl From<PathBuf> for SourceId {
+	#[inline(always)]
+	fn from(path: PathBuf) -> Self {
+		Self::Path(path)
+	}
+}
+
+impl fmt::Display for SourceId {
+	/// Displays the path of files, the name of inline sources, `<stdin>` for
+	/// the standard input and `<synthetic:n>` for synthesized sources.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Path(path) => path.display().fmt(f),
+			Self::Inline { name } => name.fmt(f),
+			Self::Stdin => write!(f, "<stdin>"),
+			Self::Synthetic(n) => write!(f, "<synthetic:{n}>"),
+		}
+	}
+}
@@ -1,4 +1,9 @@
+use crate::{CompactSpan, Merge, Meta};
+use std::cmp::Ordering;
+use std::fmt;
 use std::ops::{Index, IndexMut, Range};
+use std::rc::Rc;
+use std::sync::Arc;
 
 /// Range of bytes in a source file.
 ///
@@ -26,6 +31,15 @@ impl Span {
 		}
 	}
 
+	/// Creates a new `Span` by providing the index of its starting byte and its length in bytes.
+	#[inline(always)]
+	pub fn with_len(start: usize, len: usize) -> Self {
+		Self {
+			start,
+			end: start + len,
+		}
+	}
+
 	/// Size of the span in bytes.
 	#[inline(always)]
 	pub fn len(&self) -> usize {
@@ -86,6 +100,65 @@ impl Span {
 		self.end = std::cmp::max(self.start, end);
 	}
 
+	/// Sets the size of the span in bytes, by moving its end position.
+	#[inline(always)]
+	pub fn set_len(&mut self, len: usize) {
+		self.end = self.start + len
+	}
+
+	/// Removes the first `len` bytes from the span and returns their span.
+	///
+	/// If `len` is greater than the length of the span, the whole span is
+	/// taken and this span becomes empty.
+	#[inline(always)]
+	pub fn take_prefix(&mut self, len: usize) -> Self {
+		let end = std::cmp::min(self.start + len, self.end);
+		let prefix = Self::new(self.start, end);
+		self.start = end;
+		prefix
+	}
+
+	/// Shifts both ends of the span forward by `offset`.
+	///
+	/// This converts a span relative to a region starting at `offset` into
+	/// an absolute span.
+	#[inline(always)]
+	pub fn rebase(&self, offset: usize) -> Self {
+		Self::new(self.start + offset, self.end + offset)
+	}
+
+	/// Returns the sub-span at the given `range`, relative to the start of
+	/// this span.
+	///
+	/// The result is clamped to stay inside this span.
+	///
+	/// ```
+	/// use locspan::Span;
+	///
+	/// let span = Span::new(10, 20);
+	/// assert_eq!(span.subspan(2..5), Span::new(12, 15));
+	/// assert_eq!(span.subspan(8..15), Span::new(18, 20));
+	/// ```
+	#[inline(always)]
+	pub fn subspan(&self, range: impl Into<Self>) -> Self {
+		let range = range.into();
+		let start = self.start + std::cmp::min(range.start, self.len());
+		let end = self.start + std::cmp::min(range.end, self.len());
+		Self::new(start, end)
+	}
+
+	/// Returns the sub-span at the given `range`, relative to the start of
+	/// this span, or `None` if it is not inside this span.
+	#[inline(always)]
+	pub fn checked_subspan(&self, range: impl Into<Self>) -> Option<Self> {
+		let range = range.into();
+		if range.end <= self.len() {
+			Some(Self::new(self.start + range.start, self.start + range.end))
+		} else {
+			None
+		}
+	}
+
 	/// Computes the union of two spans.
 	///
 	/// If the two spans do not overlap,
@@ -98,6 +171,14 @@ impl Span {
 		}
 	}
 
+	/// Computes the union of all the given spans.
+	///
+	/// Returns `None` if `spans` is empty.
+	#[inline(always)]
+	pub fn union_all(spans: impl IntoIterator<Item = Self>) -> Option<Self> {
+		spans.into_iter().reduce(|a, b| a.union(b))
+	}
+
 	/// Extend this span to include `other`.
 	///
 	/// This is the *in-place* version of [`union`](Self::union).
@@ -121,6 +202,25 @@ impl Span {
 		}
 	}
 
+	/// Computes the span strictly between two disjoint spans.
+	///
+	/// The order of the two spans does not matter.
+	/// Returns `None` if the two spans touch or overlap.
+	#[inline(always)]
+	pub fn gap(&self, other: Self) -> Option<Self> {
+		let (first, second) = if self.start <= other.start {
+			(*self, other)
+		} else {
+			(other, *self)
+		};
+
+		if first.end < second.start {
+			Some(Self::new(first.end, second.start))
+		} else {
+			None
+		}
+	}
+
 	/// Clear the span by moving its start position to its end position.
 	#[inline(always)]
 	pub fn clear(&mut self) {
@@ -133,6 +233,14 @@ impl Span {
 		self.end.into()
 	}
 
+	/// Return the "previous" span: the empty span ending at the start of this one.
+	///
+	/// This is the backward counterpart of [`next`](Self::next).
+	#[inline(always)]
+	pub fn prev(&self) -> Self {
+		self.start.into()
+	}
+
 	/// Push `count` bytes to the span.
 	///
 	/// Move its end position by `count`.
@@ -140,6 +248,229 @@ impl Span {
 	pub fn push(&mut self, count: usize) {
 		self.end += count
 	}
+
+	/// Push the given character to the span.
+	///
+	/// Move its end position by the length of the UTF-8 encoding of `c`.
+	#[inline(always)]
+	pub fn push_char(&mut self, c: char) {
+		self.push(c.len_utf8())
+	}
+
+	/// Push the given string to the span.
+	///
+	/// Move its end position by the length in bytes of `s`.
+	#[inline(always)]
+	pub fn push_str(&mut self, s: &str) {
+		self.push(s.len())
+	}
+
+	/// Pull `count` bytes to the span.
+	///
+	/// Move its start position backward by `count`.
+	/// This is the backward counterpart of [`push`](Self::push).
+	///
+	/// The start position saturates at `0`: pulling more bytes than there
+	/// are before the span moves its start to the beginning of the source.
+	///
+	/// ```
+	/// # use locspan::Span;
+	/// let mut span = Span::new(2, 4);
+	/// span.pull(5);
+	/// assert_eq!(span, Span::new(0, 4));
+	/// ```
+	#[inline(always)]
+	pub fn pull(&mut self, count: usize) {
+		self.start = self.start.saturating_sub(count)
+	}
+
+	/// Moves the start position of the span to `start`, if it is before the
+	/// current start position.
+	///
+	/// The span is never shrunk.
+	/// This is the backward counterpart of [`extend_to`](Self::extend_to).
+	#[inline(always)]
+	pub fn extend_start_to(&mut self, start: usize) {
+		self.start = std::cmp::min(self.start, start)
+	}
+
+	/// Push `count` bytes to the span, checking for overflow.
+	///
+	/// Returns `false` and leaves the span unchanged if the end position
+	/// would overflow.
+	#[inline(always)]
+	#[must_use]
+	pub fn checked_push(&mut self, count: usize) -> bool {
+		match self.end.checked_add(count) {
+			Some(end) => {
+				self.end = end;
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Push `count` bytes to the span, saturating the end position at
+	/// `usize::MAX` instead of overflowing.
+	#[inline(always)]
+	pub fn saturating_push(&mut self, count: usize) {
+		self.end = self.end.saturating_add(count)
+	}
+
+	/// Moves the end position of the span to `end`, if it is after the
+	/// current end position.
+	///
+	/// The span is never shrunk.
+	#[inline(always)]
+	pub fn extend_to(&mut self, end: usize) {
+		self.end = std::cmp::max(self.end, end)
+	}
+
+	/// Moves the end position of the span to `end`.
+	///
+	/// Returns `false` and leaves the span unchanged if `end` is before the
+	/// current end position.
+	#[inline(always)]
+	#[must_use]
+	pub fn checked_extend_to(&mut self, end: usize) -> bool {
+		if end >= self.end {
+			self.end = end;
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Adjusts the span so that it starts and ends on `char` boundaries of
+	/// the given `source` text, so that indexing `source` with it never panics.
+	///
+	/// The span is widened to include partially covered characters, and
+	/// clamped to the length of `source`.
+	#[inline(always)]
+	pub fn snap_to_char_boundaries(&self, source: &str) -> Self {
+		Self::new(
+			floor_char_boundary(source, self.start),
+			ceil_char_boundary(source, self.end),
+		)
+	}
+
+	/// Checks if the span covers more than one line of the given `source` text.
+	///
+	/// A line break ending the span does not count as the start of a new line.
+	/// Returns `false` if the span is not inside `source` or does not fall on
+	/// `char` boundaries.
+	#[inline(always)]
+	pub fn is_multiline(&self, source: &str) -> bool {
+		self.line_count(source).is_some_and(|n| n > 1)
+	}
+
+	/// Returns the number of lines of the given `source` text covered by the span.
+	///
+	/// An empty span always covers one line.
+	/// A line break ending the span does not count as the start of a new line.
+	/// Returns `None` if the span is not inside `source` or does not fall on
+	/// `char` boundaries.
+	///
+	/// ```
+	/// use locspan::Span;
+	///
+	/// let source = "a\nb\nc";
+	/// assert_eq!(Span::new(0, 3).line_count(source), Some(2));
+	/// assert_eq!(Span::new(0, 10).line_count(source), None);
+	/// ```
+	pub fn line_count(&self, source: &str) -> Option<usize> {
+		let text = source.get(self.range())?;
+		let text = text.strip_suffix('\n').unwrap_or(text);
+		Some(1 + text.bytes().filter(|b| *b == b'\n').count())
+	}
+
+	/// Returns the span of the first line covered by the span in the given
+	/// `source` text, without its line break.
+	///
+	/// Returns `None` if the span is not inside `source` or does not fall on
+	/// `char` boundaries.
+	pub fn first_line(&self, source: &str) -> Option<Self> {
+		let text = source.get(self.range())?;
+		let line = match text.find('\n') {
+			Some(i) => &text[..i],
+			None => text,
+		};
+		let line = line.strip_suffix('\r').unwrap_or(line);
+		Some(Self::new(self.start, self.start + line.len()))
+	}
+
+	/// Compares two spans by their start position only.
+	///
+	/// Unlike the derived `Ord` implementation, spans with the same start are
+	/// considered equal, so that a stable sort preserves their original order.
+	#[inline(always)]
+	pub fn cmp_by_start(&self, other: &Self) -> Ordering {
+		self.start.cmp(&other.start)
+	}
+
+	/// Compares two spans by their end position only.
+	#[inline(always)]
+	pub fn cmp_by_end(&self, other: &Self) -> Ordering {
+		self.end.cmp(&other.end)
+	}
+
+	/// Compares two spans by their length only.
+	#[inline(always)]
+	pub fn cmp_by_len(&self, other: &Self) -> Ordering {
+		self.len().cmp(&other.len())
+	}
+}
+
+/// Finds the last `char` boundary of `source` at or before `offset`.
+///
+/// Returns the length of `source` if `offset` is past its end.
+pub fn floor_char_boundary(source: &str, offset: usize) -> usize {
+	if offset >= source.len() {
+		source.len()
+	} else {
+		(0..=offset)
+			.rev()
+			.find(|i| source.is_char_boundary(*i))
+			.unwrap_or(0)
+	}
+}
+
+/// Finds the first `char` boundary of `source` at or after `offset`.
+///
+/// Returns the length of `source` if `offset` is past its end.
+pub fn ceil_char_boundary(source: &str, offset: usize) -> usize {
+	if offset >= source.len() {
+		source.len()
+	} else {
+		(offset..source.len())
+			.find(|i| source.is_char_boundary(*i))
+			.unwrap_or(source.len())
+	}
+}
+
+/// Sorts spanned values using the given span comparison function.
+///
+/// The sort is stable.
+///
+/// ```
+/// use locspan::{sort_spans, Span};
+///
+/// let mut spans = [Span::new(2, 8), Span::new(0, 8), Span::new(1, 3)];
+/// sort_spans(&mut spans, Span::cmp_by_end);
+/// assert_eq!(spans, [Span::new(1, 3), Span::new(2, 8), Span::new(0, 8)]);
+/// ```
+pub fn sort_spans<T: Spanned<Span = Span>>(
+	items: &mut [T],
+	mut cmp: impl FnMut(&Span, &Span) -> Ordering,
+) {
+	items.sort_by(|a, b| cmp(&a.span(), &b.span()))
+}
+
+impl fmt::Display for Span {
+	/// Formats the span as `start..end`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}..{}", self.start, self.end)
+	}
 }
 
 impl From<usize> for Span {
@@ -154,6 +485,13 @@ impl From<Range<usize>> for Span {
 	}
 }
 
+/// Collects the union of the spans, or `None` if there are no spans.
+impl FromIterator<Span> for Option<Span> {
+	fn from_iter<I: IntoIterator<Item = Span>>(iter: I) -> Self {
+		Span::union_all(iter)
+	}
+}
+
 impl From<Span> for Range<usize> {
 	fn from(span: Span) -> Self {
 		Self {
@@ -172,7 +510,7 @@ impl IntoIterator for Span {
 	}
 }
 
-impl<'a> IntoIterator for &'a Span {
+impl IntoIterator for &Span {
 	type Item = usize;
 	type IntoIter = Range<usize>;
 
@@ -210,6 +548,20 @@ impl IndexMut<Span> for String {
 }
 
 /// Value with a span.
+///
+/// This trait is object safe, so heterogeneous spanned values can be stored
+/// as trait objects, which are themselves `Spanned`.
+///
+/// ```
+/// use locspan::{Location, Span, Spanned};
+///
+/// let nodes: Vec<Box<dyn Spanned<Span = Span>>> = vec![
+///   Box::new(Span::new(0, 1)),
+///   Box::new(Location::new("file", Span::new(2, 4))),
+/// ];
+///
+/// assert_eq!(nodes[1].span(), Span::new(2, 4));
+/// ```
 pub trait Spanned {
 	type Span;
 
@@ -224,11 +576,105 @@ impl Spanned for Span {
 	}
 }
 
+macro_rules! dyn_spanned {
+	($($bounds:tt)*) => {
+		impl<'a, S> Spanned for &'a (dyn Spanned<Span = S> $($bounds)* + 'a) {
+			type Span = S;
+
+			fn span(&self) -> Self::Span {
+				(**self).span()
+			}
+		}
+
+		impl<'a, S> Spanned for Box<dyn Spanned<Span = S> $($bounds)* + 'a> {
+			type Span = S;
+
+			fn span(&self) -> Self::Span {
+				(**self).span()
+			}
+		}
+	};
+}
+
+dyn_spanned!();
+dyn_spanned!(+ Send);
+dyn_spanned!(+ Send + Sync);
+
+/// The span of a value with metadata is the span of its metadata.
+impl<T, M: Spanned> Spanned for Meta<T, M> {
+	type Span = M::Span;
+
+	fn span(&self) -> Self::Span {
+		self.1.span()
+	}
+}
+
+impl<T: ?Sized + Spanned> Spanned for Rc<T> {
+	type Span = T::Span;
+
+	fn span(&self) -> Self::Span {
+		(**self).span()
+	}
+}
+
+impl<T: ?Sized + Spanned> Spanned for Arc<T> {
+	type Span = T::Span;
+
+	fn span(&self) -> Self::Span {
+		(**self).span()
+	}
+}
+
 /// Value with an optional span.
+///
+/// Every [`MaybeLocated`](crate::MaybeLocated) value is spanned by its
+/// optional location, including options, references and boxes of
+/// [`Located`](crate::Located) values. Options, references and boxes of
+/// spans, and of values annotated with a span, are also spanned:
+///
+/// ```
+/// use locspan::{Location, MaybeSpanned, Meta, Span};
+///
+/// let token = Meta(1u32, Span::new(0, 1));
+/// assert_eq!(Some(token).optional_span(), Some(Span::new(0, 1)));
+/// assert_eq!((&token).optional_span(), Some(Span::new(0, 1)));
+/// assert_eq!(Box::new(Span::new(2, 3)).optional_span(), Some(Span::new(2, 3)));
+///
+/// let location = Location::new("file", Span::new(4, 5));
+/// assert_eq!(Some(location).optional_span(), Some(Span::new(4, 5)));
+/// assert_eq!(None::<Span>.optional_span(), None);
+/// ```
+///
+/// It is object safe, and trait objects are themselves spanned:
+///
+/// ```
+/// use locspan::{MaybeSpanned, Span};
+///
+/// let nodes: Vec<Box<dyn MaybeSpanned<Span = Span>>> = vec![
+///   Box::new(Span::new(0, 1)),
+///   Box::new(None::<Span>),
+/// ];
+///
+/// let spans: Vec<_> = nodes.iter().map(MaybeSpanned::optional_span).collect();
+/// assert_eq!(spans, [Some(Span::new(0, 1)), None]);
+/// ```
 pub trait MaybeSpanned {
 	type Span;
 
 	fn optional_span(&self) -> Option<Self::Span>;
+
+	/// Returns the span, or `default` if there is none.
+	fn span_or(&self, default: Self::Span) -> Self::Span {
+		self.optional_span().unwrap_or(default)
+	}
+
+	/// Returns the span, or computes one using `f` if there is none.
+	fn span_or_else(&self, f: impl FnOnce() -> Self::Span) -> Self::Span
+	where
+		Self: Sized,
+	{
+		self.optional_span().unwrap_or_else(f)
+	}
 }
 
 impl MaybeSpanned for Span {
@@ -239,6 +685,157 @@ impl MaybeSpanned for Span {
 	}
 }
 
+impl<T: Spanned, E: Spanned<Span = T::Span>> MaybeSpanned for Result<T, E> {
+	type Span = T::Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		match self {
+			Ok(t) => Some(t.span()),
+			Err(e) => Some(e.span()),
+		}
+	}
+}
+
+impl<T> MaybeSpanned for Meta<T, Span> {
+	type Span = Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		Some(self.1)
+	}
+}
+
+impl<T> MaybeSpanned for Meta<T, CompactSpan> {
+	type Span = CompactSpan;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		Some(self.1)
+	}
+}
+
+// A generic `MaybeSpanned` implementation for options, references and boxes
+// of any `Spanned` value would overlap with the blanket implementation for
+// `MaybeLocated` types, since `Location` is both located and spanned.
+// Located payloads are covered by the blanket implementation, and the span
+// payloads of this crate are covered here.
+macro_rules! span_payload {
+	($([$($param:ident),*] $ty:ty),*) => {
+		$(
+			impl<$($param),*> MaybeSpanned for Option<$ty> {
+				type Span = <$ty as Spanned>::Span;
+
+				fn optional_span(&self) -> Option<Self::Span> {
+					self.as_ref().map(Spanned::span)
+				}
+			}
+
+			impl<$($param),*> MaybeSpanned for &$ty {
+				type Span = <$ty as Spanned>::Span;
+
+				fn optional_span(&self) -> Option<Self::Span> {
+					Some(Spanned::span(*self))
+				}
+			}
+
+			impl<$($param),*> MaybeSpanned for Box<$ty> {
+				type Span = <$ty as Spanned>::Span;
+
+				fn optional_span(&self) -> Option<Self::Span> {
+					Some(Spanned::span(&**self))
+				}
+			}
+		)*
+	};
+}
+
+span_payload! {
+	[] Span,
+	[] CompactSpan,
+	[T] Meta<T, Span>,
+	[T] Meta<T, CompactSpan>
+}
+
+macro_rules! dyn_maybe_spanned {
+	($($bounds:tt)*) => {
+		impl<'a, S> MaybeSpanned for &'a (dyn MaybeSpanned<Span = S> $($bounds)* + 'a) {
+			type Span = S;
+
+			fn optional_span(&self) -> Option<Self::Span> {
+				(**self).optional_span()
+			}
+		}
+
+		impl<'a, S> MaybeSpanned for Box<dyn MaybeSpanned<Span = S> $($bounds)* + 'a> {
+			type Span = S;
+
+			fn optional_span(&self) -> Option<Self::Span> {
+				(**self).optional_span()
+			}
+		}
+	};
+}
+
+dyn_maybe_spanned!();
+dyn_maybe_spanned!(+ Send);
+dyn_maybe_spanned!(+ Send + Sync);
+
+impl<T: ?Sized + MaybeSpanned> MaybeSpanned for Rc<T> {
+	type Span = T::Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		T::optional_span(self)
+	}
+}
+
+impl<T: ?Sized + MaybeSpanned> MaybeSpanned for Arc<T> {
+	type Span = T::Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		T::optional_span(self)
+	}
+}
+
+/// The span of a slice is the union of the spans of its elements.
+///
+/// An empty slice has no span.
+impl<T: Spanned> MaybeSpanned for [T]
+where
+	T::Span: Merge,
+{
+	type Span = T::Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		self.iter().map(T::span).reduce(Merge::merge)
+	}
+}
+
+/// The span of an array is the union of the spans of its elements.
+///
+/// An empty array has no span.
+impl<T: Spanned, const N: usize> MaybeSpanned for [T; N]
+where
+	T::Span: Merge,
+{
+	type Span = T::Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		self.as_slice().optional_span()
+	}
+}
+
+/// The span of a vector is the union of the spans of its elements.
+///
+/// An empty vector has no span.
+impl<T: Spanned> MaybeSpanned for Vec<T>
+where
+	T::Span: Merge,
+{
+	type Span = T::Span;
+
+	fn optional_span(&self) -> Option<Self::Span> {
+		self.as_slice().optional_span()
+	}
+}
+
 /// Value with a mutable span.
 pub trait SpannedMut: Spanned {
 	fn span_mut(&mut self) -> &mut Self::Span;
@@ -0,0 +1,85 @@
+use crate::Span;
+
+/// Lexer utility keeping track of the span of the current token.
+///
+/// ```
+/// use locspan::{Span, SpanTracker};
+///
+/// let mut tracker = SpanTracker::new();
+/// tracker.advance_str("let");
+/// assert_eq!(tracker.commit(), Span::new(0, 3));
+///
+/// tracker.advance_char(' ');
+/// tracker.skip();
+///
+/// tracker.advance_char('é');
+/// assert_eq!(tracker.current(), Span::new(4, 6));
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SpanTracker {
+	/// Span of the current token.
+	span: Span,
+}
+
+impl SpanTracker {
+	/// Creates a new tracker starting at the beginning of the source.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new tracker starting at the given byte position.
+	#[inline(always)]
+	pub fn starting_at(offset: usize) -> Self {
+		Self {
+			span: offset.into(),
+		}
+	}
+
+	/// Returns the current position, at the end of the current token.
+	#[inline(always)]
+	pub fn offset(&self) -> usize {
+		self.span.end()
+	}
+
+	/// Adds `n` bytes to the current token.
+	#[inline(always)]
+	pub fn advance(&mut self, n: usize) {
+		self.span.push(n)
+	}
+
+	/// Adds the given character to the current token.
+	#[inline(always)]
+	pub fn advance_char(&mut self, c: char) {
+		self.span.push_char(c)
+	}
+
+	/// Adds the given string to the current token.
+	#[inline(always)]
+	pub fn advance_str(&mut self, s: &str) {
+		self.span.push_str(s)
+	}
+
+	/// Returns the span of the current token.
+	#[inline(always)]
+	pub fn current(&self) -> Span {
+		self.span
+	}
+
+	/// Returns the span of the current token and starts a new token at its
+	/// end.
+	#[inline(always)]
+	pub fn commit(&mut self) -> Span {
+		let span = self.span;
+		self.span.clear();
+		span
+	}
+
+	/// Discards the current token and starts a new token at its end.
+	///
+	/// Useful to skip whitespaces and comments.
+	#[inline(always)]
+	pub fn skip(&mut self) {
+		self.span.clear()
+	}
+}
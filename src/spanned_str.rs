@@ -0,0 +1,176 @@
+use crate::{Meta, Span, Spanned};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// String slice with its span.
+///
+/// The span of the slice is expected to have the same length as the slice,
+/// so that sub-slices can be given their own span.
+///
+/// It dereferences to the underlying `str`, but its own methods
+/// (such as [`trim`](Self::trim) or [`split`](Self::split)) keep track of
+/// the span of the resulting pieces.
+///
+/// ```
+/// use locspan::{Span, SpannedStr};
+///
+/// let s = SpannedStr::from_source("  hello world ");
+/// let words: Vec<_> = s.split_whitespace().map(|w| w.span()).collect();
+/// assert_eq!(words, [Span::new(2, 7), Span::new(8, 13)]);
+/// assert_eq!(s.trim().as_str(), "hello world");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct SpannedStr<'a>(pub Meta<&'a str, Span>);
+
+impl<'a> SpannedStr<'a> {
+	/// Creates a new spanned string.
+	#[inline(always)]
+	pub fn new(value: &'a str, span: Span) -> Self {
+		Self(Meta(value, span))
+	}
+
+	/// Creates a new spanned string from the whole `source` text.
+	#[inline(always)]
+	pub fn from_source(source: &'a str) -> Self {
+		Self::new(source, Span::new(0, source.len()))
+	}
+
+	/// Creates a new spanned string by slicing `source` with `span`.
+	#[inline(always)]
+	pub fn slice(source: &'a str, span: Span) -> Self {
+		Self::new(&source[span], span)
+	}
+
+	/// Returns the string slice.
+	#[inline(always)]
+	pub fn as_str(&self) -> &'a str {
+		self.0 .0
+	}
+
+	/// Returns the span of the string.
+	#[inline(always)]
+	pub fn span(&self) -> Span {
+		self.0 .1
+	}
+
+	/// Unwraps the spanned string.
+	#[inline(always)]
+	pub fn into_inner(self) -> Meta<&'a str, Span> {
+		self.0
+	}
+
+	/// Gives its span to a `piece` of this string.
+	///
+	/// ## Panics
+	///
+	/// Panics if `piece` is not a sub-slice of this string.
+	pub fn sub(&self, piece: &'a str) -> Self {
+		let offset = (piece.as_ptr() as usize)
+			.checked_sub(self.as_str().as_ptr() as usize)
+			.filter(|offset| offset + piece.len() <= self.as_str().len())
+			.expect("not a sub-slice");
+		Self::new(
+			piece,
+			Span::with_len(self.span().start() + offset, piece.len()),
+		)
+	}
+
+	/// Returns the sub-string in the given byte range, relative to the start of this
+	/// string.
+	#[inline(always)]
+	pub fn get(&self, range: std::ops::Range<usize>) -> Option<Self> {
+		self.as_str().get(range).map(|piece| self.sub(piece))
+	}
+
+	/// Removes leading and trailing whitespaces, adjusting the span.
+	#[inline(always)]
+	pub fn trim(&self) -> Self {
+		self.sub(self.as_str().trim())
+	}
+
+	/// Removes leading whitespaces, adjusting the span.
+	#[inline(always)]
+	pub fn trim_start(&self) -> Self {
+		self.sub(self.as_str().trim_start())
+	}
+
+	/// Removes trailing whitespaces, adjusting the span.
+	#[inline(always)]
+	pub fn trim_end(&self) -> Self {
+		self.sub(self.as_str().trim_end())
+	}
+
+	/// Splits the string by the given `delimiter`, giving its span to each
+	/// piece.
+	pub fn split(&self, delimiter: char) -> impl Iterator<Item = Self> + 'a {
+		let this = *self;
+		self.as_str()
+			.split(delimiter)
+			.map(move |piece| this.sub(piece))
+	}
+
+	/// Splits the string by whitespaces, giving its span to each piece.
+	pub fn split_whitespace(&self) -> impl Iterator<Item = Self> + 'a {
+		let this = *self;
+		self.as_str()
+			.split_whitespace()
+			.map(move |piece| this.sub(piece))
+	}
+
+	/// Splits the string into lines, giving its span to each line.
+	pub fn lines(&self) -> impl Iterator<Item = Self> + 'a {
+		let this = *self;
+		self.as_str().lines().map(move |piece| this.sub(piece))
+	}
+
+	/// Parses the string into a value, keeping the span.
+	///
+	/// In case of error, the error is also given the span of the string.
+	#[inline(always)]
+	pub fn parse<T: FromStr>(&self) -> Result<Meta<T, Span>, Meta<T::Err, Span>> {
+		match self.as_str().parse() {
+			Ok(t) => Ok(Meta(t, self.span())),
+			Err(e) => Err(Meta(e, self.span())),
+		}
+	}
+}
+
+impl<'a> Deref for SpannedStr<'a> {
+	type Target = str;
+
+	#[inline(always)]
+	fn deref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<'a> Spanned for SpannedStr<'a> {
+	type Span = Span;
+
+	#[inline(always)]
+	fn span(&self) -> Span {
+		self.0 .1
+	}
+}
+
+impl<'a> From<Meta<&'a str, Span>> for SpannedStr<'a> {
+	#[inline(always)]
+	fn from(value: Meta<&'a str, Span>) -> Self {
+		Self(value)
+	}
+}
+
+impl<'a> From<SpannedStr<'a>> for Meta<&'a str, Span> {
+	#[inline(always)]
+	fn from(value: SpannedStr<'a>) -> Self {
+		value.0
+	}
+}
+
+impl<'a> fmt::Display for SpannedStr<'a> {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
@@ -0,0 +1,47 @@
+use crate::{Meta, Span};
+
+/// Data with `Span` metadata.
+///
+/// This is the single-file counterpart of [`Loc`](crate::Loc), for tools
+/// that do not need to track the source file of values.
+pub type SpannedValue<T> = Meta<T, Span>;
+
+impl<T> SpannedValue<T> {
+	/// Returns the value's span.
+	#[inline(always)]
+	pub fn span(&self) -> Span {
+		self.1
+	}
+
+	/// Returns a mutable reference to the value's span.
+	#[inline(always)]
+	pub fn span_mut(&mut self) -> &mut Span {
+		&mut self.1
+	}
+
+	/// Sets the value's span and returns the previous one.
+	#[inline(always)]
+	pub fn set_span(&mut self, span: Span) -> Span {
+		std::mem::replace(&mut self.1, span)
+	}
+
+	/// Extends the value's span to include `other`, and returns itself.
+	///
+	/// ```
+	/// use locspan::{Meta, Span};
+	///
+	/// let ident = Meta("x", Span::new(4, 5));
+	/// assert_eq!(ident.union_span(Span::new(0, 3)).span(), Span::new(0, 5));
+	/// ```
+	#[inline(always)]
+	pub fn union_span(mut self, other: Span) -> Self {
+		self.1.append(other);
+		self
+	}
+
+	/// Maps the value's span.
+	#[inline(always)]
+	pub fn map_span(self, f: impl FnOnce(Span) -> Span) -> Self {
+		Meta(self.0, f(self.1))
+	}
+}
@@ -0,0 +1,106 @@
+use crate::{CompactSpan, FileId, Location, Span};
+use std::hash::Hasher;
+
+/// Portable, stable hashing.
+///
+/// Contrarily to the [`Hash`](std::hash::Hash) trait, the sequence of bytes
+/// fed to the hasher by this trait is guaranteed to be identical across
+/// platforms and compiler versions: integers are written as fixed-size
+/// little-endian byte arrays (`usize` as `u64`), and strings are prefixed by
+/// their length.
+/// Combined with a stable hashing algorithm, it can be used to compute
+/// fingerprints (e.g. for incremental compilation).
+pub trait StableHash {
+	/// Feeds this value into the given hasher.
+	fn stable_hash<H: Hasher>(&self, state: &mut H);
+}
+
+macro_rules! stable_hash_int {
+	($($ty:ty),*) => {
+		$(
+			impl StableHash for $ty {
+				#[inline(always)]
+				fn stable_hash<H: Hasher>(&self, state: &mut H) {
+					state.write(&self.to_le_bytes())
+				}
+			}
+		)*
+	};
+}
+
+stable_hash_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl StableHash for usize {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		(*self as u64).stable_hash(state)
+	}
+}
+
+impl StableHash for isize {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		(*self as i64).stable_hash(state)
+	}
+}
+
+impl StableHash for bool {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		(*self as u8).stable_hash(state)
+	}
+}
+
+impl StableHash for str {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		self.len().stable_hash(state);
+		state.write(self.as_bytes())
+	}
+}
+
+impl StableHash for String {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		self.as_str().stable_hash(state)
+	}
+}
+
+impl<T: ?Sized + StableHash> StableHash for &T {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		T::stable_hash(self, state)
+	}
+}
+
+impl StableHash for Span {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		self.start().stable_hash(state);
+		self.end().stable_hash(state)
+	}
+}
+
+impl StableHash for CompactSpan {
+	/// Hashes the span as the equivalent [`Span`], so both representations
+	/// have the same stable hash.
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		Span::from(*self).stable_hash(state)
+	}
+}
+
+impl StableHash for FileId {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		self.index().stable_hash(state)
+	}
+}
+
+impl<F: StableHash, S: StableHash> StableHash for Location<F, S> {
+	#[inline(always)]
+	fn stable_hash<H: Hasher>(&self, state: &mut H) {
+		self.file().stable_hash(state);
+		self.span_ref().stable_hash(state)
+	}
+}
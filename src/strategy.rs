@@ -0,0 +1,62 @@
+//! [`proptest`](https://crates.io/crates/proptest) strategies.
+//!
+//! The [`Arbitrary`](proptest::arbitrary::Arbitrary) trait is also
+//! implemented for `Span`, `Location` and `Meta`.
+use crate::{Location, Meta, Span};
+use proptest::{
+	arbitrary::{any, Arbitrary},
+	strategy::{BoxedStrategy, Strategy},
+};
+
+/// Strategy generating spans included in a source of `len` bytes.
+///
+/// Generated spans respect the `start <= end` invariant.
+pub fn span_in(len: usize) -> impl Strategy<Value = Span> {
+	(0..=len, 0..=len).prop_map(|(a, b)| Span::new(std::cmp::min(a, b), std::cmp::max(a, b)))
+}
+
+/// Strategy generating locations from the given file and span strategies.
+pub fn location<F: std::fmt::Debug, S: std::fmt::Debug>(
+	file: impl Strategy<Value = F>,
+	span: impl Strategy<Value = S>,
+) -> impl Strategy<Value = Location<F, S>> {
+	(file, span).prop_map(|(file, span)| Location::new(file, span))
+}
+
+/// Strategy generating values with metadata from the given value and
+/// metadata strategies.
+pub fn meta<T: std::fmt::Debug, M: std::fmt::Debug>(
+	value: impl Strategy<Value = T>,
+	metadata: impl Strategy<Value = M>,
+) -> impl Strategy<Value = Meta<T, M>> {
+	(value, metadata).prop_map(|(t, m)| Meta(t, m))
+}
+
+impl Arbitrary for Span {
+	type Parameters = ();
+	type Strategy = BoxedStrategy<Self>;
+
+	fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+		(any::<usize>(), any::<usize>())
+			.prop_map(|(a, b)| Span::new(std::cmp::min(a, b), std::cmp::max(a, b)))
+			.boxed()
+	}
+}
+
+impl<F: Arbitrary + 'static, S: Arbitrary + 'static> Arbitrary for Location<F, S> {
+	type Parameters = (F::Parameters, S::Parameters);
+	type Strategy = BoxedStrategy<Self>;
+
+	fn arbitrary_with((f, s): Self::Parameters) -> Self::Strategy {
+		location(F::arbitrary_with(f), S::arbitrary_with(s)).boxed()
+	}
+}
+
+impl<T: Arbitrary + 'static, M: Arbitrary + 'static> Arbitrary for Meta<T, M> {
+	type Parameters = (T::Parameters, M::Parameters);
+	type Strategy = BoxedStrategy<Self>;
+
+	fn arbitrary_with((t, m): Self::Parameters) -> Self::Strategy {
+		meta(T::arbitrary_with(t), M::arbitrary_with(m)).boxed()
+	}
+}
@@ -178,6 +178,71 @@ impl<T> AsMut<T> for Stripped<T> {
 	}
 }
 
+/// Content-addressed value.
+///
+/// The identity of a `ContentAddressed<T>` is given by its stripped value
+/// only: its `Eq` and `Hash` implementations rely on [`StrippedEq`] and
+/// [`StrippedHash`]. It can be used directly as a `HashMap`/`HashSet` key to
+/// deduplicate values that only differ by their metadata, without stripping
+/// them, while still carrying the metadata of the first inserted value.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use locspan::{ContentAddressed, Loc, Location, Span};
+///
+/// let mut set = HashSet::new();
+/// assert!(set.insert(ContentAddressed(Loc(0u32, Location::new("a", Span::new(0, 1))))));
+/// assert!(!set.insert(ContentAddressed(Loc(0u32, Location::new("b", Span::new(2, 4))))));
+/// assert_eq!(set.len(), 1);
+///
+/// let first = set.into_iter().next().unwrap().unwrap();
+/// assert_eq!(*first.file(), "a");
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct ContentAddressed<T: ?Sized>(pub T);
+
+impl<T> ContentAddressed<T> {
+	#[inline]
+	pub fn unwrap(self) -> T {
+		self.0
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for ContentAddressed<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<T> ops::Deref for ContentAddressed<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> AsRef<T> for ContentAddressed<T> {
+	fn as_ref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T: StrippedPartialEq> PartialEq for ContentAddressed<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.stripped_eq(&other.0)
+	}
+}
+
+impl<T: StrippedEq> Eq for ContentAddressed<T> {}
+
+impl<T: StrippedHash> Hash for ContentAddressed<T> {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.0.stripped_hash(state)
+	}
+}
+
 macro_rules! primitive {
 	($($id:ident),*) => {
 		$(
@@ -20,7 +20,7 @@ impl<T: StrippedHash> StrippedHash for Stripped<T> {
 	}
 }
 
-impl<'t, T: StrippedHash> StrippedHash for &'t T {
+impl<T: StrippedHash> StrippedHash for &T {
 	fn stripped_hash<H: Hasher>(&self, state: &mut H) {
 		T::stripped_hash(*self, state)
 	}
@@ -20,7 +20,7 @@ impl<T: StrippedOrd> StrippedOrd for Stripped<T> {
 	}
 }
 
-impl<'t, T: StrippedOrd> StrippedOrd for &'t T {
+impl<T: StrippedOrd> StrippedOrd for &T {
 	fn stripped_cmp(&self, other: &Self) -> Ordering {
 		T::stripped_cmp(*self, *other)
 	}
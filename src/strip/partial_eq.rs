@@ -47,7 +47,7 @@ impl<T: StrippedPartialEq<U>, U> StrippedPartialEq<Stripped<U>> for Stripped<T>
 	}
 }
 
-impl<'u, 't, U, T: StrippedPartialEq<U>> StrippedPartialEq<&'u U> for &'t T {
+impl<'u, U, T: StrippedPartialEq<U>> StrippedPartialEq<&'u U> for &T {
 	fn stripped_eq(&self, other: &&'u U) -> bool {
 		T::stripped_eq(*self, *other)
 	}
@@ -86,7 +86,7 @@ impl<K: Eq + Hash, V: StrippedPartialEq<W>, W> StrippedPartialEq<HashMap<K, W>>
 		self.len() == other.len()
 			&& self
 				.iter()
-				.all(|(key, value)| other.get(key).map_or(false, |v| value.stripped_eq(v)))
+				.all(|(key, value)| other.get(key).is_some_and(|v| value.stripped_eq(v)))
 	}
 }
 
@@ -98,7 +98,7 @@ impl<K: Eq + Hash, V: StrippedPartialEq<W>, W> StrippedPartialEq<hashbrown::Hash
 		self.len() == other.len()
 			&& self
 				.iter()
-				.all(|(key, value)| other.get(key).map_or(false, |v| value.stripped_eq(v)))
+				.all(|(key, value)| other.get(key).is_some_and(|v| value.stripped_eq(v)))
 	}
 }
 
@@ -110,6 +110,6 @@ impl<K: Eq + Hash, V: StrippedPartialEq<W>, W> StrippedPartialEq<indexmap::Index
 		self.len() == other.len()
 			&& self
 				.iter()
-				.all(|(key, value)| other.get(key).map_or(false, |v| value.stripped_eq(v)))
+				.all(|(key, value)| other.get(key).is_some_and(|v| value.stripped_eq(v)))
 	}
 }
@@ -20,7 +20,7 @@ impl<T: StrippedPartialOrd<U>, U> StrippedPartialOrd<Stripped<U>> for Stripped<T
 	}
 }
 
-impl<'u, 't, U, T: StrippedPartialOrd<U>> StrippedPartialOrd<&'u U> for &'t T {
+impl<'u, U, T: StrippedPartialOrd<U>> StrippedPartialOrd<&'u U> for &T {
 	fn stripped_partial_cmp(&self, other: &&'u U) -> Option<Ordering> {
 		T::stripped_partial_cmp(*self, *other)
 	}
@@ -0,0 +1,107 @@
+use crate::{ColumnEncoding, LineIndex, Location};
+use std::fmt;
+use std::io;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+/// Styles used to pretty print locations.
+#[derive(Clone, Debug)]
+pub struct PrettyStyle {
+	/// Style of the file.
+	pub file: ColorSpec,
+
+	/// Style of the line and column numbers.
+	pub position: ColorSpec,
+}
+
+impl Default for PrettyStyle {
+	/// Bold file name, with cyan line and column numbers.
+	fn default() -> Self {
+		let mut file = ColorSpec::new();
+		file.set_bold(true);
+
+		let mut position = ColorSpec::new();
+		position.set_fg(Some(Color::Cyan));
+
+		Self { file, position }
+	}
+}
+
+impl<F> Location<F> {
+	/// Returns a value pretty printing the location as `file:line:column`,
+	/// with colors, using the given line index of its file.
+	///
+	/// Lines and columns are displayed starting from 1, and columns are
+	/// counted in characters, as with [`Location::display_with`].
+	#[inline(always)]
+	pub fn pretty<'a>(&'a self, index: &'a LineIndex<'a>) -> PrettyLocation<'a, F> {
+		PrettyLocation {
+			location: self,
+			index,
+			style: PrettyStyle::default(),
+		}
+	}
+}
+
+/// Colored location printer.
+///
+/// Created by the [`Location::pretty`] method.
+/// Its `Display` implementation prints the location without colors.
+pub struct PrettyLocation<'a, F> {
+	location: &'a Location<F>,
+	index: &'a LineIndex<'a>,
+	style: PrettyStyle,
+}
+
+impl<F: fmt::Display> PrettyLocation<'_, F> {
+	/// Sets the styles used to print the location.
+	#[inline(always)]
+	pub fn with_style(mut self, style: PrettyStyle) -> Self {
+		self.style = style;
+		self
+	}
+
+	/// Prints the location to the given colored output.
+	///
+	/// ```
+	/// use locspan::{LineIndex, Location, Span};
+	/// use termcolor::{Ansi, NoColor};
+	///
+	/// let index = LineIndex::new("a\nbc");
+	/// let location = Location::new("main.rs", Span::new(3, 4));
+	///
+	/// let mut output = NoColor::new(Vec::new());
+	/// location.pretty(&index).write_to(&mut output).unwrap();
+	/// assert_eq!(output.into_inner(), b"main.rs:2:2");
+	///
+	/// let mut output = Ansi::new(Vec::new());
+	/// location.pretty(&index).write_to(&mut output).unwrap();
+	/// assert_eq!(output.into_inner(), b"\x1b[0m\x1b[1mmain.rs\x1b[0m:\x1b[0m\x1b[36m2:2\x1b[0m");
+	/// ```
+	pub fn write_to(&self, output: &mut impl WriteColor) -> io::Result<()> {
+		output.set_color(&self.style.file)?;
+		write!(output, "{}", self.location.file())?;
+		output.reset()?;
+		write!(output, ":")?;
+		output.set_color(&self.style.position)?;
+		match self.position() {
+			Some((line, column)) => write!(output, "{line}:{column}")?,
+			None => write!(output, "{}", self.location.span())?,
+		}
+		output.reset()
+	}
+
+	/// Returns the 1-based line and column of the location, if it is in the
+	/// indexed source.
+	fn position(&self) -> Option<(usize, usize)> {
+		let position = self
+			.index
+			.position_with(self.location.span().start(), ColumnEncoding::Utf32)?;
+		Some((position.line + 1, position.column + 1))
+	}
+}
+
+impl<F: fmt::Display> fmt::Display for PrettyLocation<'_, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.location.display_with(self.index).fmt(f)
+	}
+}
@@ -0,0 +1,28 @@
+use crate::Span;
+use std::num::TryFromIntError;
+use text_size::{TextRange, TextSize};
+
+impl From<TextRange> for Span {
+	fn from(range: TextRange) -> Self {
+		Self::new(range.start().into(), range.end().into())
+	}
+}
+
+/// Fails if one of the span bounds does not fit in a `u32`.
+impl TryFrom<Span> for TextRange {
+	type Error = TryFromIntError;
+
+	fn try_from(span: Span) -> Result<Self, Self::Error> {
+		Ok(TextRange::new(
+			TextSize::try_from(span.start())?,
+			TextSize::try_from(span.end())?,
+		))
+	}
+}
+
+/// Creates an empty span at the given position.
+impl From<TextSize> for Span {
+	fn from(pos: TextSize) -> Self {
+		usize::from(pos).into()
+	}
+}
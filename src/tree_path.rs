@@ -0,0 +1,232 @@
+use crate::Merge;
+use std::fmt;
+
+/// Segment of a [`TreePath`].
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum TreePathSegment {
+	/// Map (or object) key.
+	Key(String),
+
+	/// Array index.
+	Index(usize),
+}
+
+impl From<String> for TreePathSegment {
+	#[inline(always)]
+	fn from(key: String) -> Self {
+		Self::Key(key)
+	}
+}
+
+impl From<&str> for TreePathSegment {
+	#[inline(always)]
+	fn from(key: &str) -> Self {
+		Self::Key(key.to_owned())
+	}
+}
+
+impl From<usize> for TreePathSegment {
+	#[inline(always)]
+	fn from(index: usize) -> Self {
+		Self::Index(index)
+	}
+}
+
+impl fmt::Display for TreePathSegment {
+	/// Formats the segment as a JSON pointer reference token, where `~` is
+	/// escaped as `~0` and `/` as `~1`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Key(key) => {
+				for c in key.chars() {
+					match c {
+						'~' => f.write_str("~0")?,
+						'/' => f.write_str("~1")?,
+						c => fmt::Write::write_char(f, c)?,
+					}
+				}
+
+				Ok(())
+			}
+			Self::Index(index) => index.fmt(f),
+		}
+	}
+}
+
+/// Path to a value inside a tree of structured data.
+///
+/// This can be used instead of [`Span`](crate::Span) as metadata for values
+/// extracted from structured formats (JSON, YAML, TOML, etc.) where byte
+/// offsets are not available, for instance to validate configuration files.
+///
+/// ```
+/// use locspan::{Meta, TreePath};
+///
+/// let path = TreePath::new().key("servers").index(3).key("port");
+/// let port = Meta(8080, path);
+/// assert_eq!(port.metadata().to_string(), "/servers/3/port");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct TreePath(Vec<TreePathSegment>);
+
+impl TreePath {
+	/// Creates a new empty path, pointing to the root of the tree.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the segments of the path.
+	#[inline(always)]
+	pub fn segments(&self) -> &[TreePathSegment] {
+		&self.0
+	}
+
+	/// Returns the number of segments.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks if the path is empty, pointing to the root of the tree.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the parent path, if any.
+	pub fn parent(&self) -> Option<Self> {
+		self.0.split_last().map(|(_, parent)| Self(parent.to_vec()))
+	}
+
+	/// Returns the last segment, if any.
+	#[inline(always)]
+	pub fn last(&self) -> Option<&TreePathSegment> {
+		self.0.last()
+	}
+
+	/// Appends a segment to the path.
+	#[inline(always)]
+	pub fn push(&mut self, segment: impl Into<TreePathSegment>) {
+		self.0.push(segment.into())
+	}
+
+	/// Removes the last segment of the path, and returns it.
+	#[inline(always)]
+	pub fn pop(&mut self) -> Option<TreePathSegment> {
+		self.0.pop()
+	}
+
+	/// Appends a segment to the path, and returns itself.
+	#[inline(always)]
+	pub fn join(mut self, segment: impl Into<TreePathSegment>) -> Self {
+		self.push(segment);
+		self
+	}
+
+	/// Appends a key segment to the path, and returns itself.
+	#[inline(always)]
+	pub fn key(self, key: impl Into<String>) -> Self {
+		self.join(TreePathSegment::Key(key.into()))
+	}
+
+	/// Appends an index segment to the path, and returns itself.
+	#[inline(always)]
+	pub fn index(self, index: usize) -> Self {
+		self.join(TreePathSegment::Index(index))
+	}
+
+	/// Appends all the segments of `other` to the path.
+	#[inline(always)]
+	pub fn append(&mut self, other: &Self) {
+		self.0.extend_from_slice(&other.0)
+	}
+
+	/// Appends all the segments of `other` to the path, and returns itself.
+	///
+	/// Typically used to locate a value relative to its parent.
+	#[inline(always)]
+	pub fn concat(mut self, other: &Self) -> Self {
+		self.append(other);
+		self
+	}
+
+	/// Checks if `self` is a prefix of `other`.
+	#[inline(always)]
+	pub fn is_prefix_of(&self, other: &Self) -> bool {
+		other.0.starts_with(&self.0)
+	}
+
+	/// Returns the longest common prefix of the two paths.
+	pub fn common_prefix(&self, other: &Self) -> Self {
+		Self(
+			self.0
+				.iter()
+				.zip(&other.0)
+				.take_while(|(a, b)| a == b)
+				.map(|(a, _)| a.clone())
+				.collect(),
+		)
+	}
+}
+
+impl Merge for TreePath {
+	/// Computes the longest common prefix of the two paths, pointing to the
+	/// closest common ancestor.
+	#[inline(always)]
+	fn merge(self, other: Self) -> Self {
+		self.common_prefix(&other)
+	}
+}
+
+impl From<Vec<TreePathSegment>> for TreePath {
+	#[inline(always)]
+	fn from(segments: Vec<TreePathSegment>) -> Self {
+		Self(segments)
+	}
+}
+
+impl<S: Into<TreePathSegment>> FromIterator<S> for TreePath {
+	fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+		Self(iter.into_iter().map(Into::into).collect())
+	}
+}
+
+impl<S: Into<TreePathSegment>> Extend<S> for TreePath {
+	fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+		self.0.extend(iter.into_iter().map(Into::into))
+	}
+}
+
+impl<'a> IntoIterator for &'a TreePath {
+	type Item = &'a TreePathSegment;
+	type IntoIter = std::slice::Iter<'a, TreePathSegment>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter()
+	}
+}
+
+impl IntoIterator for TreePath {
+	type Item = TreePathSegment;
+	type IntoIter = std::vec::IntoIter<TreePathSegment>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl fmt::Display for TreePath {
+	/// Formats the path as a JSON pointer (RFC 6901), e.g. `/a/b/3`.
+	///
+	/// The empty path is formatted as the empty string.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for segment in &self.0 {
+			write!(f, "/{segment}")?
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,52 @@
+use crate::{ceil_char_boundary, floor_char_boundary, Span};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+
+/// Finds the last grapheme cluster boundary at or before `offset`.
+fn floor_grapheme_boundary(source: &str, offset: usize) -> usize {
+	let offset = floor_char_boundary(source, offset);
+
+	let mut cursor = GraphemeCursor::new(offset, source.len(), true);
+	if cursor.is_boundary(source, 0).unwrap() {
+		offset
+	} else {
+		cursor.prev_boundary(source, 0).unwrap().unwrap_or(0)
+	}
+}
+
+/// Finds the first grapheme cluster boundary at or after `offset`.
+fn ceil_grapheme_boundary(source: &str, offset: usize) -> usize {
+	let offset = ceil_char_boundary(source, offset);
+
+	let mut cursor = GraphemeCursor::new(offset, source.len(), true);
+	if cursor.is_boundary(source, 0).unwrap() {
+		offset
+	} else {
+		cursor
+			.next_boundary(source, 0)
+			.unwrap()
+			.unwrap_or(source.len())
+	}
+}
+
+impl Span {
+	/// Widens the span so that it starts and ends on extended grapheme
+	/// cluster boundaries of the given `source` text.
+	///
+	/// The span is also clamped to the length of `source`.
+	pub fn widen_to_graphemes(&self, source: &str) -> Self {
+		Self::new(
+			floor_grapheme_boundary(source, self.start()),
+			ceil_grapheme_boundary(source, self.end()),
+		)
+	}
+
+	/// Counts the extended grapheme clusters covered by the span in the given
+	/// `source` text.
+	///
+	/// Partially covered grapheme clusters are counted.
+	pub fn grapheme_count(&self, source: &str) -> usize {
+		source[self.widen_to_graphemes(source)]
+			.graphemes(true)
+			.count()
+	}
+}
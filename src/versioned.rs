@@ -0,0 +1,130 @@
+use crate::{Located, Location, Span, Spanned};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Access to the current version of files.
+pub trait SourceVersions<F: ?Sized> {
+	/// Returns the current version of the given file, if known.
+	fn version(&self, file: &F) -> Option<u64>;
+}
+
+impl<F: ?Sized, T: ?Sized + SourceVersions<F>> SourceVersions<F> for &T {
+	#[inline(always)]
+	fn version(&self, file: &F) -> Option<u64> {
+		T::version(self, file)
+	}
+}
+
+impl<F: ?Sized + Eq + Hash, K: Eq + Hash + Borrow<F>> SourceVersions<F> for HashMap<K, u64> {
+	#[inline(always)]
+	fn version(&self, file: &F) -> Option<u64> {
+		self.get(file).copied()
+	}
+}
+
+impl<F: ?Sized + Ord, K: Ord + Borrow<F>> SourceVersions<F> for BTreeMap<K, u64> {
+	#[inline(always)]
+	fn version(&self, file: &F) -> Option<u64> {
+		self.get(file).copied()
+	}
+}
+
+/// Location in a given version of a file.
+///
+/// Used by incremental tools (such as language servers) to detect locations
+/// referring to an outdated version of a document.
+///
+/// ```
+/// use locspan::{Location, Span, VersionedLocation};
+/// use std::collections::HashMap;
+///
+/// let mut versions = HashMap::from([("main.rs", 1)]);
+/// let location = VersionedLocation::new(Location::new("main.rs", Span::new(0, 4)), 1);
+/// assert!(location.is_current(&versions));
+///
+/// versions.insert("main.rs", 2);
+/// assert!(location.is_stale(&versions));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct VersionedLocation<F, S = Span> {
+	/// Location.
+	location: Location<F, S>,
+
+	/// Version of the file.
+	version: u64,
+}
+
+impl<F, S> VersionedLocation<F, S> {
+	/// Creates a new location in the given version of its file.
+	#[inline(always)]
+	pub fn new(location: Location<F, S>, version: u64) -> Self {
+		Self { location, version }
+	}
+
+	/// Returns the location.
+	#[inline(always)]
+	pub fn location(&self) -> &Location<F, S> {
+		&self.location
+	}
+
+	/// Returns the version of the file.
+	#[inline(always)]
+	pub fn version(&self) -> u64 {
+		self.version
+	}
+
+	/// Consumes this value and returns the location and version.
+	#[inline(always)]
+	pub fn into_parts(self) -> (Location<F, S>, u64) {
+		(self.location, self.version)
+	}
+
+	/// Consumes this value and returns the location.
+	#[inline(always)]
+	pub fn into_location(self) -> Location<F, S> {
+		self.location
+	}
+
+	/// Checks if the location refers to the current version of its file.
+	///
+	/// Returns `false` if the current version of the file is unknown.
+	#[inline(always)]
+	pub fn is_current(&self, versions: &impl SourceVersions<F>) -> bool {
+		versions.version(self.location.file()) == Some(self.version)
+	}
+
+	/// Checks if the location refers to an outdated version of its file, or
+	/// to an unknown file.
+	#[inline(always)]
+	pub fn is_stale(&self, versions: &impl SourceVersions<F>) -> bool {
+		!self.is_current(versions)
+	}
+
+	/// Returns the location if it refers to the current version of its file.
+	#[inline(always)]
+	pub fn current(self, versions: &impl SourceVersions<F>) -> Option<Location<F, S>> {
+		if self.is_current(versions) {
+			Some(self.location)
+		} else {
+			None
+		}
+	}
+}
+
+impl<F, S> Located for VersionedLocation<F, S> {
+	type File = F;
+	type Span = S;
+
+	fn location(&self) -> &Location<F, S> {
+		&self.location
+	}
+}
+
+impl<F, S: Clone> Spanned for VersionedLocation<F, S> {
+	type Span = S;
+
+	fn span(&self) -> Self::Span {
+		self.location.span()
+	}
+}
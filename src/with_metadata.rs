@@ -0,0 +1,95 @@
+use crate::Meta;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Value with metadata.
+///
+/// Implemented by [`Meta`], and intended to be implemented by custom types
+/// (such as AST nodes) that carry their metadata in a different way, so
+/// that generic passes (sorting, grouping, reporting, etc.) are not tied to
+/// the `Meta` type.
+///
+/// ```
+/// use locspan::{SortByMetadata, Span, WithMetadata};
+///
+/// struct Token {
+///   text: &'static str,
+///   span: Span,
+/// }
+///
+/// impl WithMetadata for Token {
+///   type Metadata = Span;
+///
+///   fn metadata(&self) -> &Span {
+///     &self.span
+///   }
+/// }
+///
+/// let mut tokens = [
+///   Token { text: "b", span: Span::new(2, 3) },
+///   Token { text: "a", span: Span::new(0, 1) },
+/// ];
+/// tokens.sort_by_metadata();
+/// assert_eq!(tokens[0].text, "a");
+/// ```
+pub trait WithMetadata {
+	/// Metadata type.
+	type Metadata;
+
+	/// Returns a reference to the metadata.
+	fn metadata(&self) -> &Self::Metadata;
+}
+
+impl<T, M> WithMetadata for Meta<T, M> {
+	type Metadata = M;
+
+	#[inline(always)]
+	fn metadata(&self) -> &M {
+		&self.1
+	}
+}
+
+impl<T: ?Sized + WithMetadata> WithMetadata for &T {
+	type Metadata = T::Metadata;
+
+	#[inline(always)]
+	fn metadata(&self) -> &T::Metadata {
+		T::metadata(*self)
+	}
+}
+
+impl<T: ?Sized + WithMetadata> WithMetadata for &mut T {
+	type Metadata = T::Metadata;
+
+	#[inline(always)]
+	fn metadata(&self) -> &T::Metadata {
+		T::metadata(*self)
+	}
+}
+
+impl<T: ?Sized + WithMetadata> WithMetadata for Box<T> {
+	type Metadata = T::Metadata;
+
+	#[inline(always)]
+	fn metadata(&self) -> &T::Metadata {
+		T::metadata(self)
+	}
+}
+
+impl<T: ?Sized + WithMetadata> WithMetadata for Rc<T> {
+	type Metadata = T::Metadata;
+
+	#[inline(always)]
+	fn metadata(&self) -> &T::Metadata {
+		T::metadata(self)
+	}
+}
+
+impl<T: ?Sized + WithMetadata> WithMetadata for Arc<T> {
+	type Metadata = T::Metadata;
+
+	#[inline(always)]
+	fn metadata(&self) -> &T::Metadata {
+		T::metadata(self)
+	}
+}